@@ -0,0 +1,52 @@
+//! Measures how long it takes to look up files in an embed with 10,000 files.
+//!
+//! Run with `cargo bench -p rust-embed-for-web-bench`.
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "$OUT_DIR/files"]
+#[gzip = false]
+#[br = false]
+#[zstd = false]
+struct Files;
+
+/// Runs `f` repeatedly for about a second, and prints the average time it took
+/// for each of the `operations` that `f` does.
+fn bench<F: FnMut()>(name: &str, operations: usize, mut f: F) {
+    // Warm up the caches
+    f();
+
+    let start = Instant::now();
+    let mut runs = 0;
+    while start.elapsed() < Duration::from_secs(1) {
+        f();
+        runs += 1;
+    }
+    let per_operation = start.elapsed() / (runs * operations) as u32;
+    println!("{:<24} {:>8.1?} per lookup", name, per_operation);
+}
+
+fn main() {
+    let paths: Vec<String> = Files::iter().map(|path| path.into_owned()).collect();
+    assert_eq!(paths.len(), 10_000);
+    let missing: Vec<String> = paths
+        .iter()
+        .map(|path| format!("{}.missing", path))
+        .collect();
+
+    bench("get existing file", paths.len(), || {
+        for path in &paths {
+            black_box(Files::get(black_box(path)).unwrap());
+        }
+    });
+    bench("get missing file", missing.len(), || {
+        for path in &missing {
+            assert!(black_box(Files::get(black_box(path))).is_none());
+        }
+    });
+}
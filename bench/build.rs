@@ -0,0 +1,23 @@
+//! Generates a folder with many files to embed in the benchmarks.
+use std::{env, fs, path::Path};
+
+/// How many files to generate.
+const FILES: usize = 10_000;
+/// How many files go into each subfolder.
+const FILES_PER_FOLDER: usize = 100;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let files = Path::new(&out_dir).join("files");
+    for index in 0..FILES {
+        let folder = files.join(format!("folder-{:03}", index / FILES_PER_FOLDER));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(
+            folder.join(format!("file-{:05}.txt", index)),
+            format!("This is file {}.\n", index),
+        )
+        .unwrap();
+    }
+}
@@ -1,47 +1,75 @@
-use actix_web::{web, App, HttpResponse, HttpServer, Responder};
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
 use rust_embed_for_web::*;
 
 #[derive(RustEmbed)]
 #[folder = "examples/public/"]
 struct Asset;
 
-fn handle_embedded_file(path: &str) -> HttpResponse {
+fn handle_embedded_file(req: &HttpRequest, path: &str) -> HttpResponse {
     match Asset::get(path) {
         Some(content) => {
             print_sizes(&content);
-            let mut resp = HttpResponse::Ok();
+            let condition = conditional_request(req).evaluate(&content, true);
+            let mut resp = match condition {
+                ConditionalResponse::Ok => HttpResponse::Ok(),
+                ConditionalResponse::NotModified => HttpResponse::NotModified(),
+                ConditionalResponse::PreconditionFailed => HttpResponse::PreconditionFailed(),
+            };
             resp.append_header(("ETag", content.etag()));
+            resp.append_header(("Vary", "Accept-Encoding"));
             if let Some(last_modified) = content.last_modified() {
                 resp.append_header(("Last-Modified", last_modified));
             }
-            if let Some(body) = content.data_br() {
-                // This part will only work on release builds, try running with:
-                //
-                //     cargo run --example actix --release
-                //
-                // This example might fail if the browser doesn't support br
-                // compression, a real server should respect the Accept-Encoding
-                // header. Check
-                // `https://github.com/SeriousBug/actix-web-rust-embed-responder`
-                // for a real implementation.
-                resp.append_header(("Content-Encoding", "br"));
-                resp.body(body)
-            } else {
-                resp.body(content.data())
+            if condition != ConditionalResponse::Ok {
+                return resp.finish();
+            }
+
+            // Compressed versions are only available on release builds, try
+            // running with:
+            //
+            //     cargo run --example actix --release
+            //
+            // Check `https://github.com/SeriousBug/actix-web-rust-embed-responder`
+            // for a complete implementation.
+            match content
+                .negotiate_data(header(req, "Accept-Encoding"), &DEFAULT_ENCODING_PREFERENCE)
+            {
+                Some((encoding, body)) => {
+                    if let Some(content_encoding) = encoding.content_encoding() {
+                        resp.append_header(("Content-Encoding", content_encoding));
+                    }
+                    resp.body(body)
+                }
+                None => HttpResponse::NotAcceptable().body("406 Not Acceptable"),
             }
         }
         None => HttpResponse::NotFound().body("404 Not Found"),
     }
 }
 
+fn header<'r>(req: &'r HttpRequest, name: &str) -> Option<&'r str> {
+    req.headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+}
+
+fn conditional_request(req: &HttpRequest) -> ConditionalRequest<'_> {
+    ConditionalRequest {
+        if_match: header(req, "If-Match"),
+        if_none_match: header(req, "If-None-Match"),
+        if_modified_since: header(req, "If-Modified-Since"),
+        if_unmodified_since: header(req, "If-Unmodified-Since"),
+    }
+}
+
 #[actix_web::get("/")]
-async fn index() -> impl Responder {
-    handle_embedded_file("index.html")
+async fn index(req: HttpRequest) -> impl Responder {
+    handle_embedded_file(&req, "index.html")
 }
 
 #[actix_web::get("/dist/{_:.*}")]
-async fn dist(path: web::Path<String>) -> impl Responder {
-    handle_embedded_file(&path)
+async fn dist(req: HttpRequest, path: web::Path<String>) -> impl Responder {
+    handle_embedded_file(&req, &path)
 }
 
 #[actix_web::main]
@@ -0,0 +1,19 @@
+use axum::Router;
+use rust_embed_for_web::{RustEmbed, ServeEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+struct Asset;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    // Try http://127.0.0.1:8000/static/index.html. Compressed versions are
+    // only available on release builds, try running with:
+    //
+    //     cargo run --example axum --features tower --release
+    let app = Router::new().nest_service("/static", ServeEmbed::<Asset>::new());
+
+    println!("Launching server at http://127.0.0.1:8000");
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:8000").await?;
+    axum::serve(listener, app).await
+}
@@ -0,0 +1,162 @@
+// A small script that is served along with precompressed versions of it.
+"use strict";
+
+export function greet0(name) {
+  return `Hello number 0, ${name}!`;
+}
+
+export function greet1(name) {
+  return `Hello number 1, ${name}!`;
+}
+
+export function greet2(name) {
+  return `Hello number 2, ${name}!`;
+}
+
+export function greet3(name) {
+  return `Hello number 3, ${name}!`;
+}
+
+export function greet4(name) {
+  return `Hello number 4, ${name}!`;
+}
+
+export function greet5(name) {
+  return `Hello number 5, ${name}!`;
+}
+
+export function greet6(name) {
+  return `Hello number 6, ${name}!`;
+}
+
+export function greet7(name) {
+  return `Hello number 7, ${name}!`;
+}
+
+export function greet8(name) {
+  return `Hello number 8, ${name}!`;
+}
+
+export function greet9(name) {
+  return `Hello number 9, ${name}!`;
+}
+
+export function greet10(name) {
+  return `Hello number 10, ${name}!`;
+}
+
+export function greet11(name) {
+  return `Hello number 11, ${name}!`;
+}
+
+export function greet12(name) {
+  return `Hello number 12, ${name}!`;
+}
+
+export function greet13(name) {
+  return `Hello number 13, ${name}!`;
+}
+
+export function greet14(name) {
+  return `Hello number 14, ${name}!`;
+}
+
+export function greet15(name) {
+  return `Hello number 15, ${name}!`;
+}
+
+export function greet16(name) {
+  return `Hello number 16, ${name}!`;
+}
+
+export function greet17(name) {
+  return `Hello number 17, ${name}!`;
+}
+
+export function greet18(name) {
+  return `Hello number 18, ${name}!`;
+}
+
+export function greet19(name) {
+  return `Hello number 19, ${name}!`;
+}
+
+export function greet20(name) {
+  return `Hello number 20, ${name}!`;
+}
+
+export function greet21(name) {
+  return `Hello number 21, ${name}!`;
+}
+
+export function greet22(name) {
+  return `Hello number 22, ${name}!`;
+}
+
+export function greet23(name) {
+  return `Hello number 23, ${name}!`;
+}
+
+export function greet24(name) {
+  return `Hello number 24, ${name}!`;
+}
+
+export function greet25(name) {
+  return `Hello number 25, ${name}!`;
+}
+
+export function greet26(name) {
+  return `Hello number 26, ${name}!`;
+}
+
+export function greet27(name) {
+  return `Hello number 27, ${name}!`;
+}
+
+export function greet28(name) {
+  return `Hello number 28, ${name}!`;
+}
+
+export function greet29(name) {
+  return `Hello number 29, ${name}!`;
+}
+
+export function greet30(name) {
+  return `Hello number 30, ${name}!`;
+}
+
+export function greet31(name) {
+  return `Hello number 31, ${name}!`;
+}
+
+export function greet32(name) {
+  return `Hello number 32, ${name}!`;
+}
+
+export function greet33(name) {
+  return `Hello number 33, ${name}!`;
+}
+
+export function greet34(name) {
+  return `Hello number 34, ${name}!`;
+}
+
+export function greet35(name) {
+  return `Hello number 35, ${name}!`;
+}
+
+export function greet36(name) {
+  return `Hello number 36, ${name}!`;
+}
+
+export function greet37(name) {
+  return `Hello number 37, ${name}!`;
+}
+
+export function greet38(name) {
+  return `Hello number 38, ${name}!`;
+}
+
+export function greet39(name) {
+  return `Hello number 39, ${name}!`;
+}
@@ -0,0 +1,24 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(tracked_path)");
+
+    // Telling Cargo about the folders needs an unstable API, so it's only used
+    // when the `nightly` feature is enabled and the compiler supports it.
+    #[cfg(feature = "nightly")]
+    {
+        let probe = r#"
+            #![feature(proc_macro_tracked_path)]
+            extern crate proc_macro;
+            pub fn track() {
+                proc_macro::tracked::path(".");
+            }
+        "#;
+        if autocfg::new().probe_raw(probe).is_ok() {
+            println!("cargo:rustc-cfg=tracked_path");
+        } else {
+            println!(
+                "cargo:warning=the nightly feature of rust-embed-for-web needs a nightly compiler, added files won't trigger rebuilds"
+            );
+        }
+    }
+}
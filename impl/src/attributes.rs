@@ -1,35 +1,226 @@
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
 use rust_embed_for_web_utils::Config;
-use syn::{Attribute, Expr, ExprLit, Lit, Meta, MetaNameValue};
+#[cfg(feature = "include-exclude")]
+use rust_embed_for_web_utils::Encoding;
+#[cfg(feature = "include-exclude")]
+use syn::LitBool;
+use syn::{Attribute, Expr, ExprLit, Lit, LitStr, Meta, MetaNameValue};
 
-fn parse_str(attribute: &Attribute) -> Option<String> {
-    if let Meta::NameValue(MetaNameValue {
-        value: Expr::Lit(ExprLit {
-            lit: Lit::Str(value),
+fn parse_lit<'a>(attribute: &'a Attribute, expected: &str) -> syn::Result<&'a Lit> {
+    match &attribute.meta {
+        Meta::NameValue(MetaNameValue {
+            value: Expr::Lit(ExprLit { lit, .. }),
             ..
-        }),
-        ..
-    }) = &attribute.meta
-    {
-        return Some(value.value());
+        }) => Ok(lit),
+        Meta::NameValue(MetaNameValue { value, .. }) => Err(syn::Error::new_spanned(
+            value,
+            format!("expected {}", expected),
+        )),
+        meta => Err(syn::Error::new_spanned(
+            meta,
+            format!(
+                "expected the attribute to look like `#[{} = ...]`",
+                attribute
+                    .path()
+                    .get_ident()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            ),
+        )),
     }
-    None
 }
 
-fn parse_bool(attribute: &Attribute) -> Option<bool> {
-    if let Meta::NameValue(MetaNameValue {
-        value: Expr::Lit(ExprLit {
-            lit: Lit::Bool(value),
-            ..
-        }),
-        ..
-    }) = &attribute.meta
-    {
-        return Some(value.value());
+fn parse_str(attribute: &Attribute) -> syn::Result<&LitStr> {
+    match parse_lit(attribute, "a string literal")? {
+        Lit::Str(value) => Ok(value),
+        lit => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn parse_bool(attribute: &Attribute) -> syn::Result<bool> {
+    match parse_lit(attribute, "`true` or `false`")? {
+        Lit::Bool(value) => Ok(value.value),
+        lit => Err(syn::Error::new_spanned(lit, "expected `true` or `false`")),
+    }
+}
+
+/// Check that a number parsed from a literal is in the range.
+fn check_range<T: PartialOrd + Display>(
+    lit: &Lit,
+    value: T,
+    range: RangeInclusive<T>,
+) -> syn::Result<T> {
+    if range.contains(&value) {
+        Ok(value)
+    } else {
+        Err(syn::Error::new_spanned(
+            lit,
+            format!("expected a value from {} to {}", range.start(), range.end()),
+        ))
+    }
+}
+
+/// Parse an integer attribute like `#[name = 9]`, checking that the value is
+/// in the range.
+fn parse_int_in_range<T>(attribute: &Attribute, range: RangeInclusive<T>) -> syn::Result<T>
+where
+    T: FromStr + PartialOrd + Display,
+    T::Err: Display,
+{
+    match parse_lit(attribute, "an integer")? {
+        lit @ Lit::Int(value) => check_range(lit, value.base10_parse()?, range),
+        lit => Err(syn::Error::new_spanned(lit, "expected an integer")),
+    }
+}
+
+/// Parse a number attribute like `#[name = 0.9]`, checking that the value is
+/// in the range.
+fn parse_float_in_range(attribute: &Attribute, range: RangeInclusive<f64>) -> syn::Result<f64> {
+    match parse_lit(attribute, "a number")? {
+        lit @ Lit::Float(value) => check_range(lit, value.base10_parse()?, range),
+        lit @ Lit::Int(value) => check_range(lit, value.base10_parse()?, range),
+        lit => Err(syn::Error::new_spanned(lit, "expected a number")),
+    }
+}
+
+/// A `#[folder]` attribute, along with the `#[prefix]` that comes after it.
+pub(crate) struct FolderAttribute<'a> {
+    pub(crate) folder: &'a LitStr,
+    pub(crate) prefix: Option<&'a LitStr>,
+}
+
+/// Find the folders to embed, and the prefixes for them.
+///
+/// A `#[prefix]` that comes before all the folders is shared by every folder,
+/// and is returned on its own. A `#[prefix]` that comes after a folder only
+/// applies to that folder, on top of the shared prefix. Each of them can only
+/// be set once.
+pub(crate) fn find_folders(
+    ast: &syn::DeriveInput,
+) -> syn::Result<(Option<&LitStr>, Vec<FolderAttribute<'_>>)> {
+    let mut shared_prefix: Option<&LitStr> = None;
+    let mut folders: Vec<FolderAttribute> = Vec::new();
+    for attribute in &ast.attrs {
+        if attribute.path().is_ident("folder") {
+            folders.push(FolderAttribute {
+                folder: parse_str(attribute)?,
+                prefix: None,
+            });
+        } else if attribute.path().is_ident("prefix") {
+            let prefix = parse_str(attribute)?;
+            let slot = match folders.last_mut() {
+                Some(folder) => &mut folder.prefix,
+                None => &mut shared_prefix,
+            };
+            if slot.is_some() {
+                return Err(syn::Error::new_spanned(
+                    prefix,
+                    "#[derive(RustEmbed)] must have at most one prefix for each folder, you supplied several",
+                ));
+            }
+            *slot = Some(prefix);
+        }
+    }
+    if folders.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &ast.ident,
+            "#[derive(RustEmbed)] must contain a folder attribute, like #[folder = \"public/\"]",
+        ));
+    }
+    Ok((shared_prefix, folders))
+}
+
+/// Find the value of a boolean attribute like `#[name = true]`, if it's set.
+pub(crate) fn find_bool_attribute_value(
+    ast: &syn::DeriveInput,
+    attr_name: &str,
+) -> syn::Result<Option<bool>> {
+    let mut values = ast
+        .attrs
+        .iter()
+        .filter(|attribute| attribute.path().is_ident(attr_name));
+    let value = values.next().map(parse_bool).transpose()?;
+    if let Some(duplicate) = values.next() {
+        return Err(syn::Error::new_spanned(
+            duplicate,
+            format!("#[{}] can only be set once", attr_name),
+        ));
+    }
+    Ok(value)
+}
+
+#[cfg(feature = "include-exclude")]
+fn add_glob(
+    attribute: &Attribute,
+    add: impl FnOnce(String) -> Result<(), globset::Error>,
+) -> syn::Result<()> {
+    let pattern = parse_str(attribute)?;
+    add(pattern.value()).map_err(|error| {
+        syn::Error::new_spanned(pattern, format!("invalid glob pattern: {}", error))
+    })
+}
+
+/// Add a compression rule from an attribute like
+/// `#[compress(path = "*.wasm", all = false, br = true)]`.
+///
+/// `all` sets every encoding, and the encodings listed by name override it
+/// regardless of the order they are written in.
+#[cfg(feature = "include-exclude")]
+fn add_compression_rule(config: &mut Config, attribute: &Attribute) -> syn::Result<()> {
+    let mut pattern: Option<LitStr> = None;
+    let mut all: Option<bool> = None;
+    let mut encodings: Vec<(Encoding, bool)> = Vec::new();
+    attribute.parse_nested_meta(|meta| {
+        let encoding = if meta.path.is_ident("path") {
+            pattern = Some(meta.value()?.parse()?);
+            return Ok(());
+        } else if meta.path.is_ident("all") {
+            all = Some(meta.value()?.parse::<LitBool>()?.value);
+            return Ok(());
+        } else if meta.path.is_ident("gzip") {
+            Encoding::Gzip
+        } else if meta.path.is_ident("br") {
+            Encoding::Br
+        } else if meta.path.is_ident("zstd") {
+            Encoding::Zstd
+        } else if meta.path.is_ident("deflate") {
+            Encoding::Deflate
+        } else {
+            return Err(
+                meta.error("expected one of `path`, `all`, `gzip`, `br`, `zstd`, or `deflate`")
+            );
+        };
+        encodings.push((encoding, meta.value()?.parse::<LitBool>()?.value));
+        Ok(())
+    })?;
+
+    let pattern = pattern.ok_or_else(|| {
+        syn::Error::new_spanned(
+            attribute,
+            "expected a glob pattern for the files, like `path = \"*.wasm\"`",
+        )
+    })?;
+    if let Some(all) = all {
+        for encoding in [
+            Encoding::Gzip,
+            Encoding::Br,
+            Encoding::Zstd,
+            Encoding::Deflate,
+        ] {
+            if !encodings.iter().any(|(listed, _)| *listed == encoding) {
+                encodings.push((encoding, all));
+            }
+        }
     }
-    None
+    config
+        .add_compression_rule(pattern.value(), encodings)
+        .map_err(|error| {
+            syn::Error::new_spanned(&pattern, format!("invalid glob pattern: {}", error))
+        })
 }
 
-pub(crate) fn read_attribute_config(ast: &syn::DeriveInput) -> Config {
+pub(crate) fn read_attribute_config(ast: &syn::DeriveInput) -> syn::Result<Config> {
     let mut config = Config::default();
 
     for attribute in &ast.attrs {
@@ -37,16 +228,57 @@ pub(crate) fn read_attribute_config(ast: &syn::DeriveInput) -> Config {
             let ident = ident.to_string();
             match ident.as_str() {
                 #[cfg(feature = "include-exclude")]
-                "include" => parse_str(attribute).map(|v| config.add_include(v)),
+                "include" => add_glob(attribute, |v| config.add_include(v))?,
                 #[cfg(feature = "include-exclude")]
-                "exclude" => parse_str(attribute).map(|v| config.add_exclude(v)),
-                "gzip" => parse_bool(attribute).map(|v| config.set_gzip(v)),
-                "br" => parse_bool(attribute).map(|v| config.set_br(v)),
-                "zstd" => parse_bool(attribute).map(|v| config.set_zstd(v)),
-                _ => None,
+                "exclude" => add_glob(attribute, |v| config.add_exclude(v))?,
+                #[cfg(feature = "include-exclude")]
+                "compress" => add_compression_rule(&mut config, attribute)?,
+                #[cfg(not(feature = "include-exclude"))]
+                "include" | "exclude" | "compress" => {
+                    return Err(syn::Error::new_spanned(
+                        attribute,
+                        "the `include-exclude` feature of rust-embed-for-web must be enabled to use this attribute",
+                    ))
+                }
+                #[cfg(feature = "compression-zstd")]
+                "zstd_dictionary" => config.set_zstd_dictionary(parse_bool(attribute)?),
+                #[cfg(feature = "compression-zstd")]
+                "zstd_dictionary_size" => config
+                    .set_zstd_dictionary_size(parse_int_in_range(attribute, 256..=usize::MAX)?),
+                #[cfg(not(feature = "compression-zstd"))]
+                "zstd_dictionary" | "zstd_dictionary_size" => {
+                    return Err(syn::Error::new_spanned(
+                        attribute,
+                        "the `compression-zstd` feature of rust-embed-for-web must be enabled to use this attribute",
+                    ))
+                }
+                "gzip" => config.set_gzip(parse_bool(attribute)?),
+                "br" => config.set_br(parse_bool(attribute)?),
+                "zstd" => config.set_zstd(parse_bool(attribute)?),
+                "deflate" => config.set_deflate(parse_bool(attribute)?),
+                "precompressed" => config.set_precompressed(parse_bool(attribute)?),
+                "incompressible_mime_type" => {
+                    config.add_incompressible_mime_type(parse_str(attribute)?.value())
+                }
+                "default_incompressible_mime_types" => {
+                    config.set_default_incompressible_mime_types(parse_bool(attribute)?)
+                }
+                "gzip_level" => config.set_gzip_level(parse_int_in_range(attribute, 0..=9)?),
+                "zopfli_iterations" => config
+                    .set_zopfli_iterations(parse_int_in_range(attribute, 1..=u64::MAX)?),
+                "br_quality" => config.set_br_quality(parse_int_in_range(attribute, 0..=11)?),
+                "br_window" => config.set_br_window(parse_int_in_range(attribute, 10..=24)?),
+                "zstd_level" => config.set_zstd_level(parse_int_in_range(attribute, 1..=22)?),
+                "compression_threshold" => config
+                    .set_compression_threshold(parse_float_in_range(attribute, 0.0..=1.0)?),
+                "min_compression_saving" => config
+                    .set_min_compression_saving(parse_int_in_range(attribute, 0..=u64::MAX)?),
+                "min_compression_size" => config
+                    .set_min_compression_size(parse_int_in_range(attribute, 0..=u64::MAX)?),
+                _ => {}
             };
         }
     }
 
-    config
+    Ok(config)
 }
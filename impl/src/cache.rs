@@ -0,0 +1,123 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use sha2::{Digest, Sha256};
+
+/// The environment variable that can be used to change where the compressed
+/// files are stored.
+const CACHE_DIR_VAR: &str = "RUST_EMBED_FOR_WEB_CACHE_DIR";
+
+/// Bump this if the way files are compressed changes in a way that the
+/// compression settings don't capture, to avoid using outdated cache entries.
+const CACHE_VERSION: u32 = 1;
+
+/// The folder where the compressed versions of the files are stored, so they
+/// can be embedded with `include_bytes!` and reused between builds.
+///
+/// This is the `RUST_EMBED_FOR_WEB_CACHE_DIR` environment variable if it's
+/// set, or `OUT_DIR` if the crate using the embed has a build script.
+/// Otherwise there's no cache, since a shared folder like the system temporary
+/// directory could have files planted in it by other users.
+fn cache_dir() -> Option<PathBuf> {
+    if let Some(cache_dir) = env::var_os(CACHE_DIR_VAR) {
+        return Some(PathBuf::from(cache_dir));
+    }
+    env::var_os("OUT_DIR").map(|out_dir| Path::new(&out_dir).join("rust-embed-for-web"))
+}
+
+/// A compressed version of a file.
+pub(crate) enum CachedFile {
+    /// Stored in the cache folder, so it can be embedded with `include_bytes!`.
+    Stored { path: String, len: u64 },
+    /// Couldn't be stored in the cache folder, for example because it's read
+    /// only or there is no cache folder, so the data has to be embedded
+    /// directly.
+    Uncached(Vec<u8>),
+}
+
+impl CachedFile {
+    pub(crate) fn len(&self) -> u64 {
+        match self {
+            CachedFile::Stored { len, .. } => *len,
+            CachedFile::Uncached(data) => data.len() as u64,
+        }
+    }
+}
+
+/// Get the compressed version of a file from the cache, compressing and
+/// storing it if it isn't in the cache yet.
+///
+/// Entries are keyed by the hash of the original file, the `extension` of the
+/// compressed file which identifies the codec, and the compression `settings`.
+/// Any change to the file or the settings results in a new entry, and an entry
+/// is never modified once it's written.
+///
+/// Entries are written to a temporary file first then moved into place, so
+/// concurrent builds sharing the cache can't see partially written files. If
+/// two builds compress the same file at the same time, one of them wins but
+/// both produce the same contents. If there is no cache folder or it can't be
+/// written to, the file is compressed without being cached. Nothing is stored in the
+/// cache if the compression fails.
+pub(crate) fn try_compress_cached<E, F: FnOnce() -> Result<Vec<u8>, E>>(
+    hash: &str,
+    extension: &str,
+    settings: &str,
+    compress: F,
+) -> Result<CachedFile, E> {
+    let key = Sha256::digest(format!(
+        "{}\0{}\0{}\0{}",
+        CACHE_VERSION, hash, extension, settings
+    ));
+    let dir = match cache_dir() {
+        Some(dir) => dir,
+        None => return compress().map(CachedFile::Uncached),
+    };
+    let path = dir.join(format!("{:x}.{}", key, extension));
+    // `include_bytes!` needs the path as a string.
+    let path_str = match path.to_str() {
+        Some(path_str) => path_str.to_owned(),
+        None => return compress().map(CachedFile::Uncached),
+    };
+
+    if let Ok(metadata) = fs::metadata(&path) {
+        if metadata.is_file() {
+            return Ok(CachedFile::Stored {
+                path: path_str,
+                len: metadata.len(),
+            });
+        }
+    }
+    let data = compress()?;
+    match write_atomically(&dir, &path, &data) {
+        Ok(()) => Ok(CachedFile::Stored {
+            path: path_str,
+            len: data.len() as u64,
+        }),
+        Err(_) => Ok(CachedFile::Uncached(data)),
+    }
+}
+
+fn write_atomically(dir: &Path, path: &Path, data: &[u8]) -> io::Result<()> {
+    // Makes the temporary file names unique between threads of the same build
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    fs::create_dir_all(dir)?;
+    let temp_path = dir.join(format!(
+        "{}.{}.{}.tmp",
+        path.file_name().unwrap_or_default().to_string_lossy(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let written = fs::File::create(&temp_path)
+        .and_then(|mut temp_file| temp_file.write_all(data))
+        .and_then(|()| fs::rename(&temp_path, path));
+    if written.is_err() {
+        // Don't leave a partial file behind, it's fine if there isn't one.
+        let _ = fs::remove_file(&temp_path);
+    }
+    written
+}
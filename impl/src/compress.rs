@@ -1,71 +1,32 @@
-use std::io::{BufReader, Write};
+/// The magic number that starts a `dcz` response, before the hash of the
+/// dictionary.
+const DCZ_MAGIC: [u8; 8] = [0x5e, 0x2a, 0x4d, 0x18, 0x20, 0x00, 0x00, 0x00];
 
-use brotli::enc::BrotliEncoderParams;
-use flate2::{write::GzEncoder, Compression};
-#[cfg(feature = "compression-zstd")]
-use zstd::stream::write::Encoder as ZstdEncoder;
-
-/// Only include the compressed version if it is at least this much smaller than
-/// the uncompressed version.
-const COMPRESSION_INCLUDE_THRESHOLD: f64 = 0.95;
-
-pub(crate) fn compress_gzip(data: &[u8]) -> Option<Vec<u8>> {
-    let mut data_gzip: Vec<u8> = Vec::new();
-    let mut encoder = GzEncoder::new(&mut data_gzip, Compression::default());
-    encoder
-        .write_all(data)
-        .expect("Failed to compress gzip data");
-    encoder
-        .finish()
-        .expect("Failed to finish compression of gzip data");
-
-    if data_gzip.len() < ((data.len() as f64) * COMPRESSION_INCLUDE_THRESHOLD) as usize {
-        Some(data_gzip)
-    } else {
-        None
-    }
-}
-
-pub(crate) fn compress_br(data: &[u8]) -> Option<Vec<u8>> {
-    let mut data_read = BufReader::new(data);
-    let mut data_br: Vec<u8> = Vec::new();
-    brotli::BrotliCompress(
-        &mut data_read,
-        &mut data_br,
-        &BrotliEncoderParams::default(),
-    )
-    .expect("Failed to compress br data");
-    if data_br.len() < ((data.len() as f64) * COMPRESSION_INCLUDE_THRESHOLD) as usize {
-        Some(data_br)
-    } else {
-        None
-    }
-}
-
-/// Compresses data using zstd compression.
+/// Compresses data with a zstd dictionary for the `dcz` content encoding.
 ///
-/// Returns the compressed data if it's smaller than the threshold, `None` otherwise.
-/// Uses compression level 3 as a balance between compression ratio and speed.
-#[cfg(feature = "compression-zstd")]
-pub(crate) fn compress_zstd(data: &[u8]) -> Option<Vec<u8>> {
-    let mut data_zstd: Vec<u8> = Vec::new();
-    // Level 3 provides good compression with reasonable speed for build-time compression
-    let mut encoder = ZstdEncoder::new(&mut data_zstd, 3).expect("Failed to create zstd encoder");
-    encoder
-        .write_all(data)
-        .expect("Failed to compress zstd data");
-    encoder
-        .finish()
-        .expect("Failed to finish compression of zstd data");
-
-    if data_zstd.len() < ((data.len() as f64) * COMPRESSION_INCLUDE_THRESHOLD) as usize {
-        Some(data_zstd)
-    } else {
-        None
-    }
-}
-
-#[cfg(not(feature = "compression-zstd"))]
-pub(crate) fn compress_zstd(_data: &[u8]) -> Option<Vec<u8>> {
-    None
+/// The output starts with a header holding the SHA-256 hash of the
+/// dictionary, followed by the zstd frame. The window is limited to 8 MiB,
+/// which is the most browsers are required to support.
+pub(crate) fn compress_dcz(
+    data: &[u8],
+    level: i32,
+    dictionary: &[u8],
+    dictionary_sha256: &[u8],
+) -> Vec<u8> {
+    use zstd::zstd_safe::CParameter;
+
+    let mut compressor = zstd::bulk::Compressor::with_dictionary(level, dictionary)
+        .expect("Failed to create zstd compressor with the dictionary");
+    compressor
+        .set_parameter(CParameter::WindowLog(23))
+        .expect("Failed to set the zstd window size");
+    let compressed = compressor
+        .compress(data)
+        .expect("Failed to compress dcz data");
+    let mut data_dcz =
+        Vec::with_capacity(DCZ_MAGIC.len() + dictionary_sha256.len() + compressed.len());
+    data_dcz.extend_from_slice(&DCZ_MAGIC);
+    data_dcz.extend_from_slice(dictionary_sha256);
+    data_dcz.extend_from_slice(&compressed);
+    data_dcz
 }
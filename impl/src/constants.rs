@@ -0,0 +1,112 @@
+use std::collections::{HashMap, HashSet};
+
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use rust_embed_for_web_utils::{precompressed_original, Config};
+
+use crate::folders::{list_files, Folder};
+
+/// Names of the items that the derive generates on the struct, which the
+/// constants must not collide with.
+const RESERVED_NAMES: &[&str] = &["ENTRIES"];
+
+/// The name of the constant for a file, like `IMAGES_LOGO_PNG` for
+/// `images/logo.png`.
+///
+/// ASCII letters and digits are uppercased, and anything else becomes an
+/// underscore. Names that would start with a digit get an underscore in front.
+fn constant_name(path: &str) -> String {
+    let name: String = path
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", name)
+    } else {
+        name
+    }
+}
+
+/// Generate a constant with the path of every file in the folders, so that
+/// typos in paths are caught when compiling.
+///
+/// The names leave out `names_prefix`, which is the prefix that is shared by
+/// all the folders.
+///
+/// The files are listed when compiling in debug builds too, so files that are
+/// added to the folder later don't have constants until the crate is rebuilt.
+pub(crate) fn generate_path_constants(
+    ident: &syn::Ident,
+    vis: &syn::Visibility,
+    config: &Config,
+    folders: &[Folder],
+    allow_overlap: bool,
+    names_prefix: &str,
+    attribute: &syn::Attribute,
+) -> syn::Result<TokenStream2> {
+    let (entries, errors) = list_files(folders, config, allow_overlap);
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+    let mut paths: Vec<String> = entries
+        .into_iter()
+        .map(|(_, entry)| entry.rel_path)
+        .collect();
+    // Precompressed files are hidden like they are in the embed.
+    if config.use_precompressed() {
+        let all: HashSet<String> = paths.iter().cloned().collect();
+        paths.retain(|path| {
+            !matches!(precompressed_original(path), Some((original, _)) if all.contains(original))
+        });
+    }
+
+    let mut names: HashMap<String, &str> = HashMap::new();
+    let mut constants: Vec<TokenStream2> = Vec::new();
+    for path in &paths {
+        let name = constant_name(path.strip_prefix(names_prefix).unwrap_or(path));
+        if RESERVED_NAMES.contains(&name.as_str()) {
+            return Err(syn::Error::new_spanned(
+                attribute,
+                format!(
+                    "the constant for {} would be named {}, which is already used by the embed",
+                    path, name
+                ),
+            ));
+        }
+        if name == "_" {
+            return Err(syn::Error::new_spanned(
+                attribute,
+                format!(
+                    "the constant for {} would be named _, which isn't a valid name, rename the file or disable path_constants",
+                    path
+                ),
+            ));
+        }
+        if let Some(other) = names.insert(name.clone(), path) {
+            return Err(syn::Error::new_spanned(
+                attribute,
+                format!(
+                    "{} and {} would both have a constant named {}, rename one of them or disable path_constants",
+                    other, path, name
+                ),
+            ));
+        }
+        let name = syn::Ident::new(&name, Span::call_site());
+        let doc = format!("The path of `{}`.", path);
+        constants.push(quote! {
+            #[doc = #doc]
+            #vis const #name: &'static str = #path;
+        });
+    }
+
+    Ok(quote! {
+        impl #ident {
+            #(#constants)*
+        }
+    })
+}
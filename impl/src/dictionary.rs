@@ -0,0 +1,59 @@
+use std::{fs, io};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use sha2::{Digest, Sha256};
+
+use crate::cache::{try_compress_cached, CachedFile};
+
+/// A zstd dictionary trained from the files of an embed, stored in the cache.
+pub(crate) struct TrainedDictionary {
+    /// The dictionary in the cache, to embed it from.
+    pub(crate) cached: CachedFile,
+    pub(crate) data: Vec<u8>,
+    pub(crate) sha256: Vec<u8>,
+}
+
+impl TrainedDictionary {
+    /// The hash of the dictionary in the format of the `Available-Dictionary`
+    /// header, which is a base64 encoded byte sequence wrapped with colons.
+    pub(crate) fn header_hash(&self) -> String {
+        format!(":{}:", STANDARD.encode(&self.sha256))
+    }
+
+    /// The hash of the dictionary in hex, to tell apart files compressed with
+    /// different dictionaries in the cache.
+    pub(crate) fn hex_hash(&self) -> String {
+        self.sha256.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+/// Train a zstd dictionary from the contents of some files, reusing the
+/// dictionary from the cache if it has been trained from the same files
+/// before.
+///
+/// `samples` are the hashes and contents of the files, in a stable order.
+pub(crate) fn train_dictionary(
+    samples: &[(String, &[u8])],
+    max_size: usize,
+) -> io::Result<TrainedDictionary> {
+    let mut hasher = Sha256::new();
+    for (hash, _) in samples {
+        hasher.update(hash.as_bytes());
+        hasher.update(b"\0");
+    }
+    let samples_hash = format!("{:x}", hasher.finalize());
+
+    let cached = try_compress_cached(&samples_hash, "dict", &format!("size={}", max_size), || {
+        let data: Vec<&[u8]> = samples.iter().map(|(_, data)| *data).collect();
+        zstd::dict::from_samples(&data, max_size)
+    })?;
+    let data = match &cached {
+        CachedFile::Stored { path, .. } => fs::read(path)?,
+        CachedFile::Uncached(data) => data.clone(),
+    };
+    Ok(TrainedDictionary {
+        sha256: Sha256::digest(&data).to_vec(),
+        cached,
+        data,
+    })
+}
@@ -3,7 +3,7 @@ use globset::GlobMatcher;
 use proc_macro2::TokenStream as TokenStream2;
 use rust_embed_for_web_utils::Config;
 
-use crate::embed::MakeEmbed;
+use crate::{embed::MakeEmbed, folders::Folder};
 
 impl MakeEmbed for Vec<String> {
     fn make_embed(&self) -> TokenStream2 {
@@ -32,7 +32,8 @@ impl MakeEmbed for Config {
                     let includes = includes.make_embed();
                     quote! {
                         for ele in #includes {
-                            config.add_include(ele.to_string());
+                            // The patterns were already validated by the macro
+                            config.add_include(ele.to_string()).unwrap();
                         }
                     }
                 }
@@ -50,7 +51,7 @@ impl MakeEmbed for Config {
                     let excludes = excludes.make_embed();
                     quote! {
                         for ele in #excludes {
-                            config.add_exclude(ele.to_string());
+                            config.add_exclude(ele.to_string()).unwrap();
                         }
                     }
                 }
@@ -59,35 +60,163 @@ impl MakeEmbed for Config {
             quote! {}
         };
 
+        let precompressed_embed = if self.use_precompressed() {
+            quote! { config.set_precompressed(true); }
+        } else {
+            quote! {}
+        };
+
+        // Precompressed files are used for the encodings the file would be
+        // compressed with, so they need the settings too.
+        let compression_embed = if cfg!(feature = "dynamic-compression") || self.use_precompressed()
+        {
+            compression_settings_embed(self)
+        } else {
+            quote! {}
+        };
+
         quote! {
             let mut config = rust_embed_for_web::utils::Config::new();
             #includes_embed
             #excludes_embed
+            #precompressed_embed
+            #compression_embed
             config
         }
     }
 }
 
+/// The settings that files are compressed with, so dynamic files can be
+/// compressed like they would be in a release build.
+fn compression_settings_embed(config: &Config) -> TokenStream2 {
+    let (gzip, br, zstd, deflate) = (
+        config.should_gzip(),
+        config.should_br(),
+        config.should_zstd(),
+        config.should_deflate(),
+    );
+    let gzip_level_embed = if config.is_gzip_level_set() {
+        let gzip_level = config.gzip_level();
+        quote! { config.set_gzip_level(#gzip_level); }
+    } else {
+        quote! {}
+    };
+    let (br_quality, br_window) = (config.br_quality(), config.br_window());
+    let zstd_level = config.zstd_level();
+    let compression_threshold = config.compression_threshold();
+    let min_compression_saving = config.min_compression_saving();
+    let min_compression_size = config.min_compression_size();
+    let default_incompressible_mime_types = config.use_default_incompressible_mime_types();
+    let incompressible_mime_types = config.get_incompressible_mime_types().to_vec().make_embed();
+    let rules_embed = {
+        #[cfg(feature = "include-exclude")]
+        {
+            let rules = config.get_compression_rules().map(|(pattern, encodings)| {
+                let encodings = encodings.iter().map(|(encoding, enabled)| {
+                    let encoding =
+                        syn::Ident::new(&format!("{:?}", encoding), proc_macro2::Span::call_site());
+                    quote! { (rust_embed_for_web::utils::Encoding::#encoding, #enabled) }
+                });
+                quote! {
+                    config
+                        .add_compression_rule(#pattern.to_string(), vec![#(#encodings),*])
+                        .unwrap();
+                }
+            });
+            quote! { #(#rules)* }
+        }
+        #[cfg(not(feature = "include-exclude"))]
+        quote! {}
+    };
+
+    quote! {
+        config.set_gzip(#gzip);
+        config.set_br(#br);
+        config.set_zstd(#zstd);
+        config.set_deflate(#deflate);
+        #gzip_level_embed
+        config.set_br_quality(#br_quality);
+        config.set_br_window(#br_window);
+        config.set_zstd_level(#zstd_level);
+        config.set_compression_threshold(#compression_threshold);
+        config.set_min_compression_saving(#min_compression_saving);
+        config.set_min_compression_size(#min_compression_size);
+        config.set_default_incompressible_mime_types(#default_incompressible_mime_types);
+        let incompressible_mime_types: &[&str] = #incompressible_mime_types;
+        for mime_type in incompressible_mime_types {
+            config.add_incompressible_mime_type(mime_type.to_string());
+        }
+        #rules_embed
+    }
+}
+
 pub(crate) fn generate_dynamic_impl(
     ident: &syn::Ident,
+    vis: &syn::Visibility,
     config: &Config,
-    folder_path: &str,
-    prefix: &str,
+    folders: &[Folder],
 ) -> TokenStream2 {
     let config = config.make_embed();
+    let folders_embed: Vec<TokenStream2> = folders
+        .iter()
+        .map(|folder| {
+            let (path, prefix) = (&folder.path, &folder.prefix);
+            quote! { (#path, #prefix) }
+        })
+        .collect();
+
+    let entries_error = format!(
+        "{}::ENTRIES is only available when files are embedded, since debug builds read the \
+         files when they are accessed. Enable the `always-embed` feature to embed the files in \
+         debug builds too.",
+        ident
+    );
 
     quote! {
       impl #ident {
+        /// All the files in the folder along with their paths, which is only
+        /// available when the files are embedded. Using this in a debug build
+        /// without the `always-embed` feature is a compile error.
+        #vis const ENTRIES: &'static [(&'static str, &'static rust_embed_for_web::EmbeddedFile)] =
+          panic!(#entries_error);
+
         fn get(path: &str) -> Option<rust_embed_for_web::DynamicFile> {
+          // The folders along with their prefixes, in the order they were listed
+          const FOLDERS: &[(&str, &str)] = &[#(#folders_embed),*];
           let config = { #config };
-          let path = path.strip_prefix(#prefix)?;
-          if config.should_include(path) {
-            let folder_path: std::path::PathBuf = std::convert::From::from(#folder_path);
-            let combined_path = folder_path.join(path);
-            rust_embed_for_web::DynamicFile::read_from_fs(combined_path).ok()
-          } else {
-            None
+          rust_embed_for_web::utils::read_dynamic_file(FOLDERS, &config, path)
+        }
+
+        fn iter() -> rust_embed_for_web::Filenames {
+          const FOLDERS: &[(&str, &str)] = &[#(#folders_embed),*];
+          let config = { #config };
+          let mut seen: std::collections::HashSet<String> = std::collections::HashSet::new();
+          let mut names: Vec<String> = Vec::new();
+          for (folder_path, prefix) in FOLDERS {
+            for entry in rust_embed_for_web::utils::get_files(folder_path, &config, prefix) {
+              if let Ok(entry) = entry {
+                if seen.insert(entry.rel_path.clone()) {
+                  names.push(entry.rel_path);
+                }
+              }
+            }
           }
+          // Precompressed files are hidden when they are used for their
+          // originals, like in release builds.
+          names.retain(|name| !rust_embed_for_web::utils::is_hidden_precompressed(FOLDERS, &config, name));
+          rust_embed_for_web::Filenames::Dynamic(names.into_iter())
+        }
+
+        fn read_dir(path: &str) -> Option<rust_embed_for_web::ReadDir> {
+          // Directories are made up from the files, like in release builds, so
+          // empty and excluded directories don't show up.
+          let mut directories = rust_embed_for_web::utils::directory_tree(#ident::iter());
+          let entries = directories.remove(rust_embed_for_web::utils::directory_key(path))?;
+          Some(rust_embed_for_web::ReadDir::Dynamic(entries.into_iter()))
+        }
+
+        fn is_dir(path: &str) -> bool {
+          #ident::read_dir(path).is_some()
         }
       }
 
@@ -97,6 +226,18 @@ pub(crate) fn generate_dynamic_impl(
         fn get(file_path: &str) -> Option<Self::File> {
           #ident::get(file_path)
         }
+
+        fn iter() -> rust_embed_for_web::Filenames {
+          #ident::iter()
+        }
+
+        fn read_dir(path: &str) -> Option<rust_embed_for_web::ReadDir> {
+          #ident::read_dir(path)
+        }
+
+        fn is_dir(path: &str) -> bool {
+          #ident::is_dir(path)
+        }
       }
     }
 }
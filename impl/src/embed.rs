@@ -1,7 +1,24 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
 use proc_macro2::TokenStream as TokenStream2;
-use rust_embed_for_web_utils::{get_files, Config, DynamicFile, EmbedableFile, FileEntry};
+use rust_embed_for_web_utils::{
+    compress::{compress, compression_settings, decompress},
+    directory_tree, precompressed_original, Config, DynamicFile, EmbedableFile, Encoding,
+};
 
-use crate::compress::{compress_br, compress_gzip, compress_zstd};
+use crate::{
+    cache::{try_compress_cached, CachedFile},
+    folders::{list_files, Folder},
+    parallel::parallel_map,
+};
+#[cfg(feature = "compression-zstd")]
+use crate::{
+    compress::compress_dcz,
+    dictionary::{train_dictionary, TrainedDictionary},
+};
 
 /// Anything that can be embedded into the program.
 ///
@@ -12,11 +29,40 @@ pub(crate) trait MakeEmbed {
     fn make_embed(&self) -> TokenStream2;
 }
 
-impl MakeEmbed for Vec<u8> {
+/// Data that is embedded, from a file with `include_bytes!` when possible.
+///
+/// This is much faster to compile than spelling out the bytes, and lets Cargo
+/// know that the crate needs to be rebuilt when the file changes. Compressed
+/// data that couldn't be stored in the cache is spelled out instead.
+#[derive(Clone)]
+enum EmbedBytes {
+    File(String),
+    Bytes(Arc<Vec<u8>>),
+}
+
+impl EmbedBytes {
+    fn file(path: &str) -> EmbedBytes {
+        EmbedBytes::File(path.to_owned())
+    }
+
+    fn cached(cached: &CachedFile) -> EmbedBytes {
+        match cached {
+            CachedFile::Stored { path, .. } => EmbedBytes::file(path),
+            CachedFile::Uncached(data) => EmbedBytes::Bytes(Arc::new(data.clone())),
+        }
+    }
+}
+
+impl MakeEmbed for EmbedBytes {
     fn make_embed(&self) -> TokenStream2 {
-        // Not sure why quote doesn't like it if I use #self here
-        let v = self;
-        quote! { &[#(#v),*] }
+        // Casts rather than slicing, since these are used in statics.
+        match self {
+            EmbedBytes::File(path) => quote! { include_bytes!(#path) as &'static [u8] },
+            EmbedBytes::Bytes(data) => {
+                let data = syn::LitByteStr::new(data, proc_macro2::Span::call_site());
+                quote! { #data as &'static [u8] }
+            }
+        }
     }
 }
 
@@ -44,38 +90,139 @@ impl<T: MakeEmbed> MakeEmbed for Option<T> {
     }
 }
 
-struct EmbedDynamicFile<'t> {
-    file: &'t DynamicFile,
-    config: &'t Config,
+/// The encodings that files can be compressed with, if they are enabled.
+const COMPRESSED_ENCODINGS: [Encoding; 4] = [
+    Encoding::Gzip,
+    Encoding::Br,
+    Encoding::Zstd,
+    Encoding::Deflate,
+];
+
+/// A file that is being embedded, along with the compressed versions of it.
+struct EmbedDynamicFile {
+    file: DynamicFile,
+    data: Vec<u8>,
+    full_canonical_path: String,
+    /// Precompressed versions of the file that were found in the folder.
+    precompressed: Vec<(Encoding, String)>,
+    compressed: Vec<(Encoding, EmbedBytes)>,
+    /// The file compressed with the zstd dictionary, if there is one.
+    dcz: Option<EmbedBytes>,
 }
 
-impl<'t> EmbedDynamicFile<'t> {
-    fn new(file: &'t DynamicFile, config: &'t Config) -> EmbedDynamicFile<'t> {
-        EmbedDynamicFile { file, config }
+impl EmbedDynamicFile {
+    fn read(full_canonical_path: &str) -> std::io::Result<EmbedDynamicFile> {
+        let file = DynamicFile::read_from_fs(full_canonical_path)?;
+        Ok(EmbedDynamicFile {
+            data: file.data(),
+            file,
+            full_canonical_path: full_canonical_path.to_owned(),
+            precompressed: Vec::new(),
+            compressed: Vec::new(),
+            dcz: None,
+        })
+    }
+
+    /// Use the precompressed version of the file for some encoding, if there
+    /// is one, after checking that it decompresses to the same contents.
+    fn use_precompressed(&self, encoding: Encoding) -> Option<Result<EmbedBytes, String>> {
+        let (_, path) = self
+            .precompressed
+            .iter()
+            .find(|(precompressed, _)| *precompressed == encoding)?;
+        let decompressed = std::fs::read(path)
+            .and_then(|data| decompress(encoding, &data))
+            .map_err(|error| format!("failed to decompress {}: {}", path, error));
+        Some(decompressed.and_then(|decompressed| {
+            if decompressed == self.data {
+                Ok(EmbedBytes::file(path))
+            } else {
+                Err(format!(
+                    "{} does not decompress to the contents of {}",
+                    path, self.full_canonical_path
+                ))
+            }
+        }))
+    }
+
+    /// Compress the file with some encoding, reusing the compressed version
+    /// from the cache if the file has been compressed with the same settings
+    /// before.
+    ///
+    /// Returns `None` if the file is too small to compress, or if the
+    /// compressed version is not worth including. This is safe to call from
+    /// any thread.
+    fn compress(&self, encoding: Encoding, config: &Config) -> Option<EmbedBytes> {
+        let extension = match encoding {
+            Encoding::Identity => return None,
+            Encoding::Gzip => "gz",
+            Encoding::Br => "br",
+            Encoding::Zstd => "zst",
+            Encoding::Deflate => "zz",
+        };
+        // There are no settings if the encoding isn't enabled.
+        let settings = compression_settings(encoding, config)?;
+        self.compress_cached(config, extension, &settings, |data| {
+            compress(encoding, config, data)
+        })
+    }
+
+    fn compress_cached<F: FnOnce(&[u8]) -> Option<Vec<u8>>>(
+        &self,
+        config: &Config,
+        extension: &str,
+        settings: &str,
+        compress: F,
+    ) -> Option<EmbedBytes> {
+        let len = self.data.len() as u64;
+        if !config.should_compress(len) {
+            return None;
+        }
+        let cached = try_compress_cached(&self.file.hash(), extension, settings, || {
+            compress(&self.data).ok_or(())
+        })
+        .ok()?;
+        if config.is_worth_including(len, cached.len()) {
+            Some(EmbedBytes::cached(&cached))
+        } else {
+            None
+        }
+    }
+
+    /// Compress the file with the trained zstd dictionary for the `dcz`
+    /// encoding. This is safe to call from any thread.
+    #[cfg(feature = "compression-zstd")]
+    fn compress_dcz(&self, config: &Config, dictionary: &TrainedDictionary) -> Option<EmbedBytes> {
+        let level = config.zstd_level();
+        let settings = format!("level={},dictionary={}", level, dictionary.hex_hash());
+        self.compress_cached(config, "dcz", &settings, |data| {
+            Some(compress_dcz(
+                data,
+                level,
+                &dictionary.data,
+                &dictionary.sha256,
+            ))
+        })
+    }
+
+    fn compressed(&self, encoding: Encoding) -> Option<EmbedBytes> {
+        self.compressed
+            .iter()
+            .find(|(compressed, _)| *compressed == encoding)
+            .map(|(_, data)| data.clone())
     }
 }
 
-impl<'t> MakeEmbed for EmbedDynamicFile<'t> {
+impl MakeEmbed for EmbedDynamicFile {
     fn make_embed(&self) -> TokenStream2 {
-        let file = self.file;
+        let file = &self.file;
         let name = file.name().make_embed();
-        let data = file.data();
-        let data_gzip = if self.config.should_gzip() {
-            compress_gzip(&data).make_embed()
-        } else {
-            None::<Vec<u8>>.make_embed()
-        };
-        let data_br = if self.config.should_br() {
-            compress_br(&data).make_embed()
-        } else {
-            None::<Vec<u8>>.make_embed()
-        };
-        let data_zstd = if self.config.should_zstd() {
-            compress_zstd(&data).make_embed()
-        } else {
-            None::<Vec<u8>>.make_embed()
-        };
-        let data = data.make_embed();
+        let data_gzip = self.compressed(Encoding::Gzip).make_embed();
+        let data_br = self.compressed(Encoding::Br).make_embed();
+        let data_zstd = self.compressed(Encoding::Zstd).make_embed();
+        let data_deflate = self.compressed(Encoding::Deflate).make_embed();
+        let data_dcz = self.dcz.make_embed();
+        let data = EmbedBytes::file(&self.full_canonical_path).make_embed();
         let hash = file.hash().make_embed();
         let etag = file.etag().make_embed();
         let last_modified = file.last_modified().make_embed();
@@ -89,6 +236,8 @@ impl<'t> MakeEmbed for EmbedDynamicFile<'t> {
                 #data_gzip,
                 #data_br,
                 #data_zstd,
+                #data_deflate,
+                #data_dcz,
                 #hash,
                 #etag,
                 #last_modified,
@@ -101,36 +250,257 @@ impl<'t> MakeEmbed for EmbedDynamicFile<'t> {
 
 pub(crate) fn generate_embed_impl(
     ident: &syn::Ident,
+    vis: &syn::Visibility,
     config: &Config,
-    folder_path: &str,
-    prefix: &str,
-) -> TokenStream2 {
-    let embeds: Vec<TokenStream2> = get_files(folder_path, config, prefix)
-        .filter_map(
-            |FileEntry {
-                 rel_path,
-                 full_canonical_path,
-             }| {
-                if let Ok(file) = DynamicFile::read_from_fs(full_canonical_path) {
-                    let file_embed = EmbedDynamicFile::new(&file, config).make_embed();
-                    Some(quote! {
-                        #rel_path => Some(#file_embed),
-                    })
-                } else {
-                    None
+    folders: &[Folder],
+    allow_overlap: bool,
+) -> syn::Result<TokenStream2> {
+    let mut names: Vec<&String> = Vec::new();
+    let mut statics: Vec<TokenStream2> = Vec::new();
+    let mut embeds: Vec<TokenStream2> = Vec::new();
+    // A missing folder has already been reported unless it's allowed, in which
+    // case it's skipped.
+    let (mut entries, mut errors) = list_files(folders, config, allow_overlap);
+    // Errors about a file point at the folder it's in.
+    let mut report = |folder: usize, message: String| {
+        let error = syn::Error::new_spanned(folders[folder].attribute, message);
+        match &mut errors {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        }
+    };
+
+    // Precompressed files are used for their originals instead of being
+    // embedded on their own.
+    let mut precompressed: HashMap<String, Vec<(Encoding, String)>> = HashMap::new();
+    if config.use_precompressed() {
+        let rel_paths: HashSet<String> = entries.iter().map(|(_, e)| e.rel_path.clone()).collect();
+        entries.retain(|(_, entry)| match precompressed_original(&entry.rel_path) {
+            Some((original, encoding)) if rel_paths.contains(original) => {
+                precompressed
+                    .entry(original.to_owned())
+                    .or_default()
+                    .push((encoding, entry.full_canonical_path.clone()));
+                false
+            }
+            _ => true,
+        });
+    }
+
+    // Reading and compressing the files is slow, so it's done on multiple
+    // threads. The results keep the order of the files so the generated code
+    // is the same every time.
+    let read = parallel_map(&entries, |(_, entry)| {
+        EmbedDynamicFile::read(&entry.full_canonical_path)
+    });
+    let mut files: Vec<(String, EmbedDynamicFile)> = Vec::new();
+    // The folder each file is in, kept apart from the files since spans can't
+    // be sent to other threads.
+    let mut file_folders: Vec<usize> = Vec::new();
+    for ((folder, entry), file) in entries.into_iter().zip(read) {
+        match file {
+            Ok(mut file) => {
+                if let Some(precompressed) = precompressed.remove(&entry.rel_path) {
+                    file.precompressed = precompressed;
                 }
-            },
-        )
+                files.push((entry.rel_path, file));
+                file_folders.push(folder);
+            }
+            Err(error) => report(
+                folder,
+                format!("failed to read {}: {}", entry.full_canonical_path, error),
+            ),
+        }
+    }
+
+    // Formats that are already compressed are skipped without trying.
+    let compressible: Vec<usize> = files
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, file))| config.is_compressible_mime_type(file.file.mime_type().as_deref()))
+        .map(|(index, _)| index)
         .collect();
+    let tasks: Vec<(usize, Encoding)> = compressible
+        .iter()
+        .map(|index| (*index, &files[*index].0))
+        .flat_map(|(index, rel_path)| {
+            COMPRESSED_ENCODINGS
+                .iter()
+                .filter(move |encoding| config.should_compress_with(rel_path, **encoding))
+                .map(move |encoding| (index, *encoding))
+        })
+        .collect();
+    let compressed = parallel_map(&tasks, |(index, encoding)| {
+        let file = &files[*index].1;
+        match file.use_precompressed(*encoding) {
+            Some(precompressed) => precompressed.map(Some),
+            None => Ok(file.compress(*encoding, config)),
+        }
+    });
+    for ((index, encoding), compressed) in tasks.into_iter().zip(compressed) {
+        match compressed {
+            Ok(Some(compressed)) => files[index].1.compressed.push((encoding, compressed)),
+            Ok(None) => {}
+            Err(error) => report(file_folders[index], error),
+        }
+    }
+
+    #[cfg(feature = "compression-zstd")]
+    let dictionary = if config.should_train_zstd_dictionary() {
+        // The dictionary is trained from the same files that get compressed
+        // with zstd, and only those files are compressed with it.
+        let indices: Vec<usize> = compressible
+            .iter()
+            .copied()
+            .filter(|index| config.should_compress_with(&files[*index].0, Encoding::Zstd))
+            .collect();
+        let samples: Vec<(String, &[u8])> = indices
+            .iter()
+            .map(|index| {
+                let file = &files[*index].1;
+                (file.file.hash(), file.data.as_slice())
+            })
+            .collect();
+        match train_dictionary(&samples, config.zstd_dictionary_size()) {
+            Ok(dictionary) => {
+                let dcz = parallel_map(&indices, |index| {
+                    files[*index].1.compress_dcz(config, &dictionary)
+                });
+                for (index, dcz) in indices.into_iter().zip(dcz) {
+                    files[index].1.dcz = dcz;
+                }
+                Some(dictionary)
+            }
+            Err(error) => {
+                // The dictionary is shared by all the folders, so the error
+                // points at the first one.
+                report(0, format!("failed to train the zstd dictionary: {}", error));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    #[cfg(feature = "compression-zstd")]
+    let (zstd_dictionary, zstd_dictionary_trait) = match dictionary {
+        Some(dictionary) => {
+            let data = EmbedBytes::cached(&dictionary.cached).make_embed();
+            let hash = dictionary.header_hash();
+            (
+                quote! {
+                    fn zstd_dictionary() -> Option<rust_embed_for_web::ZstdDictionary> {
+                        Some(rust_embed_for_web::ZstdDictionary::__internal_make(#data, #hash))
+                    }
+                },
+                quote! {
+                    fn zstd_dictionary() -> Option<rust_embed_for_web::ZstdDictionary> {
+                        #ident::zstd_dictionary()
+                    }
+                },
+            )
+        }
+        None => (quote! {}, quote! {}),
+    };
+    #[cfg(not(feature = "compression-zstd"))]
+    let (zstd_dictionary, zstd_dictionary_trait) = (quote! {}, quote! {});
+
+    for (index, (rel_path, file)) in files.iter().enumerate() {
+        let file_embed = file.make_embed();
+        let ident = quote::format_ident!("FILE_{}", index);
+        statics.push(quote! {
+            static #ident: rust_embed_for_web::EmbeddedFile = #file_embed;
+        });
+        embeds.push(quote! { (#rel_path, &#ident) });
+        names.push(rel_path);
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
 
-    quote! {
+    // Files are looked up with a perfect hash map, which is much faster than
+    // comparing the path to every file. The map is generated with a fixed
+    // seed, so it's the same every time.
+    let keys: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+    let hash_state = phf_generator::generate_hash(&keys);
+    let hash_key = hash_state.key;
+    let disps = hash_state
+        .disps
+        .iter()
+        .map(|(d1, d2)| quote! { (#d1, #d2) });
+    let entries = hash_state.map.iter().map(|index| {
+        let rel_path = names[*index];
+        quote! { (#rel_path, #ident::ENTRIES[#index].1) }
+    });
+
+    // Directories are looked up the same way, each with its entries.
+    let directories = directory_tree(&names);
+    let dir_keys: Vec<&str> = directories.keys().map(|dir| dir.as_str()).collect();
+    let dir_hash_state = phf_generator::generate_hash(&dir_keys);
+    let dir_hash_key = dir_hash_state.key;
+    let dir_disps = dir_hash_state
+        .disps
+        .iter()
+        .map(|(d1, d2)| quote! { (#d1, #d2) });
+    let dir_entries = dir_hash_state.map.iter().map(|index| {
+        let dir = dir_keys[*index];
+        let entries = directories[dir].iter().map(|entry| {
+            let (path, is_dir) = (entry.path(), entry.is_dir());
+            quote! { rust_embed_for_web::DirEntry::__internal_make(#path, #is_dir) }
+        });
+        quote! { (#dir, &[#(#entries),*]) }
+    });
+
+    let count = embeds.len();
+
+    Ok(quote! {
       impl #ident {
+          /// All the files in the folder along with their paths, in the same
+          /// order as `iter`.
+          ///
+          /// This is a constant, so the files can be used to build `static` and
+          /// `const` items. It points to a static with the files, so each file
+          /// is only in the binary once.
+          #vis const ENTRIES: &'static [(&'static str, &'static rust_embed_for_web::EmbeddedFile)] = {
+              #(#statics)*
+              static ENTRIES: [(&str, &rust_embed_for_web::EmbeddedFile); #count] = [#(#embeds),*];
+              &ENTRIES
+          };
+
           fn get(path: &str) -> Option<rust_embed_for_web::EmbeddedFile> {
-              match path {
-                    #(#embeds)*
-                    _ => None,
-              }
+              static FILES: rust_embed_for_web::utils::phf::Map<
+                  &'static str,
+                  &'static rust_embed_for_web::EmbeddedFile,
+              > = rust_embed_for_web::utils::phf::Map {
+                  key: #hash_key,
+                  disps: &[#(#disps),*],
+                  entries: &[#(#entries),*],
+              };
+              FILES.get(path).map(|file| **file)
+          }
+
+          fn iter() -> rust_embed_for_web::Filenames {
+              const NAMES: &[&str] = &[#(#names),*];
+              rust_embed_for_web::Filenames::Embedded(NAMES.iter())
+          }
+
+          fn read_dir(path: &str) -> Option<rust_embed_for_web::ReadDir> {
+              static DIRECTORIES: rust_embed_for_web::utils::phf::Map<
+                  &'static str,
+                  &'static [rust_embed_for_web::DirEntry],
+              > = rust_embed_for_web::utils::phf::Map {
+                  key: #dir_hash_key,
+                  disps: &[#(#dir_disps),*],
+                  entries: &[#(#dir_entries),*],
+              };
+              let entries = DIRECTORIES.get(rust_embed_for_web::utils::directory_key(path))?;
+              Some(rust_embed_for_web::ReadDir::Embedded(entries.iter()))
+          }
+
+          fn is_dir(path: &str) -> bool {
+              #ident::read_dir(path).is_some()
           }
+
+          #zstd_dictionary
       }
 
       impl rust_embed_for_web::RustEmbed for #ident {
@@ -139,6 +509,20 @@ pub(crate) fn generate_embed_impl(
         fn get(file_path: &str) -> Option<Self::File> {
           #ident::get(file_path)
         }
+
+        fn iter() -> rust_embed_for_web::Filenames {
+          #ident::iter()
+        }
+
+        fn read_dir(path: &str) -> Option<rust_embed_for_web::ReadDir> {
+          #ident::read_dir(path)
+        }
+
+        fn is_dir(path: &str) -> bool {
+          #ident::is_dir(path)
+        }
+
+        #zstd_dictionary_trait
       }
-    }
+    })
 }
@@ -0,0 +1,143 @@
+use std::{collections::HashMap, env, path::Path};
+
+use rust_embed_for_web_utils::{get_files, Config, FileEntry};
+use syn::LitStr;
+
+/// A folder that is embedded, possibly along with other folders.
+pub(crate) struct Folder<'a> {
+    /// The absolute path of the folder.
+    pub(crate) path: String,
+    /// The prefix for the paths of the files in the folder, including the
+    /// prefix that is shared by all the folders.
+    pub(crate) prefix: String,
+    /// The attribute the folder came from, to point errors at.
+    pub(crate) attribute: &'a LitStr,
+}
+
+impl Folder<'_> {
+    pub(crate) fn is_dir(&self) -> bool {
+        Path::new(&self.path).is_dir()
+    }
+}
+
+/// Get the absolute path of a folder from its attribute, expanding variables
+/// if the `interpolate-folder-path` feature is enabled.
+///
+/// Relative paths are based on the location of the `Cargo.toml` file.
+pub(crate) fn resolve_folder_path(folder_attr: &LitStr) -> syn::Result<String> {
+    let folder_path = folder_attr.value();
+    #[cfg(feature = "interpolate-folder-path")]
+    let folder_path = shellexpand::full(&folder_path)
+        .map_err(|error| {
+            syn::Error::new_spanned(
+                folder_attr,
+                format!("failed to expand the folder path: {}", error),
+            )
+        })?
+        .to_string();
+
+    if Path::new(&folder_path).is_relative() {
+        let manifest_dir = env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+            syn::Error::new_spanned(
+                folder_attr,
+                "CARGO_MANIFEST_DIR is not set, relative folder paths can only be used when building with Cargo",
+            )
+        })?;
+        Ok(Path::new(&manifest_dir)
+            .join(folder_path)
+            .to_str()
+            .ok_or_else(|| {
+                syn::Error::new_spanned(folder_attr, "the folder path is not valid unicode")
+            })?
+            .to_owned())
+    } else {
+        Ok(folder_path)
+    }
+}
+
+/// Tell Cargo to rebuild the crate when files are added to or removed from the
+/// folders.
+///
+/// Cargo notices this through the modification times of the directories, so
+/// every directory inside of the folders is tracked. A missing folder tracks
+/// its parent instead, so creating the folder triggers a rebuild.
+#[cfg(tracked_path)]
+pub(crate) fn track_folders(folders: &[Folder]) {
+    for folder in folders {
+        if !folder.is_dir() {
+            if let Some(parent) = Path::new(&folder.path).parent().and_then(Path::to_str) {
+                if Path::new(parent).is_dir() {
+                    proc_macro::tracked::path(parent);
+                }
+            }
+            continue;
+        }
+        let directories = walkdir::WalkDir::new(&folder.path)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_dir());
+        for directory in directories {
+            if let Some(path) = directory.path().to_str() {
+                proc_macro::tracked::path(path);
+            }
+        }
+    }
+}
+
+/// List the files in all the folders that exist, in the order the folders
+/// were listed, along with the index of the folder each file is in.
+///
+/// When the same path is in several folders, it's an error unless
+/// `allow_overlap` is set, in which case the file from the folder listed first
+/// is used. Errors are returned along with the files that could be listed, so
+/// the caller can report all of them at once.
+pub(crate) fn list_files(
+    folders: &[Folder],
+    config: &Config,
+    allow_overlap: bool,
+) -> (Vec<(usize, FileEntry)>, Option<syn::Error>) {
+    let mut files: Vec<(usize, FileEntry)> = Vec::new();
+    let mut errors: Option<syn::Error> = None;
+    let mut report = |error: syn::Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+
+    // The folder each path was found in first
+    let mut seen: HashMap<String, &Folder> = HashMap::new();
+    for (index, folder) in folders.iter().enumerate() {
+        if !folder.is_dir() {
+            continue;
+        }
+        for entry in get_files(&folder.path, config, &folder.prefix) {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(error) => {
+                    report(syn::Error::new_spanned(
+                        folder.attribute,
+                        format!("failed to read the folder: {}", error),
+                    ));
+                    continue;
+                }
+            };
+            match seen.get(&entry.rel_path) {
+                Some(_) if allow_overlap => {}
+                Some(first) => report(syn::Error::new_spanned(
+                    folder.attribute,
+                    format!(
+                        "{} is in both {} and {}, add #[allow_overlap = true] to use the file from the folder listed first",
+                        entry.rel_path,
+                        first.attribute.value(),
+                        folder.attribute.value()
+                    ),
+                )),
+                None => {
+                    seen.insert(entry.rel_path.clone(), folder);
+                    files.push((index, entry));
+                }
+            }
+        }
+    }
+    (files, errors)
+}
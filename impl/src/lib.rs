@@ -5,91 +5,181 @@
 //! re-exports any necessary parts from this crate.
 #![recursion_limit = "1024"]
 #![forbid(unsafe_code)]
+#![cfg_attr(tracked_path, feature(proc_macro_tracked_path))]
 #[macro_use]
 extern crate quote;
 extern crate proc_macro;
 
 mod attributes;
+mod cache;
+#[cfg(feature = "compression-zstd")]
 mod compress;
+mod constants;
+#[cfg(feature = "compression-zstd")]
+mod dictionary;
 mod dynamic;
 mod embed;
+mod folders;
+mod parallel;
 
-use attributes::read_attribute_config;
+use attributes::{find_bool_attribute_value, find_folders, read_attribute_config};
+use constants::generate_path_constants;
 use dynamic::generate_dynamic_impl;
 use embed::generate_embed_impl;
+use folders::{list_files, resolve_folder_path, Folder};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
-use std::{env, path::Path};
-use syn::{Data, DeriveInput, Expr, ExprLit, Fields, Lit, Meta, MetaNameValue};
+use syn::{spanned::Spanned, Data, DeriveInput, Fields, LitStr};
 
-/// Find all pairs of the `name = "value"` attribute from the derive input
-fn find_attribute_values(ast: &syn::DeriveInput, attr_name: &str) -> Vec<String> {
-    ast.attrs
-        .iter()
-        .filter(|value| value.path().is_ident(attr_name))
-        .map(|attr| &attr.meta)
-        .filter_map(|meta| match meta {
-            Meta::NameValue(MetaNameValue {
-                value:
-                    Expr::Lit(ExprLit {
-                        lit: Lit::Str(val), ..
-                    }),
-                ..
-            }) => Some(val.value()),
-            _ => None,
-        })
-        .collect()
-}
-
-fn impl_rust_embed_for_web(ast: &syn::DeriveInput) -> TokenStream2 {
-    match ast.data {
-        Data::Struct(ref data) => match data.fields {
+fn impl_rust_embed_for_web(ast: &syn::DeriveInput) -> syn::Result<TokenStream2> {
+    match &ast.data {
+        Data::Struct(data) => match &data.fields {
             Fields::Unit => {}
-            _ => panic!("RustEmbed can only be derived for unit structs"),
+            fields => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "RustEmbed can only be derived for unit structs",
+                ))
+            }
         },
-        _ => panic!("RustEmbed can only be derived for unit structs"),
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &ast.ident,
+                "RustEmbed can only be derived for unit structs",
+            ))
+        }
     };
 
-    let mut folder_paths = find_attribute_values(ast, "folder");
-    if folder_paths.len() != 1 {
-        panic!("#[derive(RustEmbed)] must contain one and only one folder attribute");
+    let (shared_prefix, folder_attrs) = find_folders(ast)?;
+    let shared_prefix = shared_prefix.map(LitStr::value).unwrap_or_default();
+    let folders = folder_attrs
+        .iter()
+        .map(|folder_attr| {
+            Ok(Folder {
+                path: resolve_folder_path(folder_attr.folder)?,
+                prefix: format!(
+                    "{}{}",
+                    shared_prefix,
+                    folder_attr.prefix.map(LitStr::value).unwrap_or_default()
+                ),
+                attribute: folder_attr.folder,
+            })
+        })
+        .collect::<syn::Result<Vec<Folder>>>()?;
+    let config = read_attribute_config(ast)?;
+
+    let allow_missing = find_bool_attribute_value(ast, "allow_missing")?.unwrap_or(false);
+    let allow_overlap = find_bool_attribute_value(ast, "allow_overlap")?.unwrap_or(false);
+    let is_dynamic = cfg!(debug_assertions) && !cfg!(feature = "always-embed");
+    let mut missing_folder_warnings: Vec<TokenStream2> = Vec::new();
+    for folder in folders.iter().filter(|folder| !folder.is_dir()) {
+        if allow_missing {
+            continue;
+        }
+        if is_dynamic {
+            // Files are read at runtime in debug builds so the folder might get
+            // created later, for example by a frontend build. Only warn about it.
+            let message = format!(
+                "the folder {} does not exist, the embed will be empty until it is created",
+                folder.path
+            );
+            missing_folder_warnings.push(quote_spanned! {folder.attribute.span()=>
+                const _: () = {
+                    #[deprecated(note = #message)]
+                    #[allow(non_upper_case_globals)]
+                    const folder_does_not_exist: () = ();
+                    folder_does_not_exist
+                };
+            });
+        } else {
+            return Err(syn::Error::new_spanned(
+                folder.attribute,
+                format!(
+                    "the folder {} does not exist, add #[allow_missing = true] if the embed should be empty instead",
+                    folder.path
+                ),
+            ));
+        }
     }
-    let folder_path = folder_paths.remove(0);
-    #[cfg(feature = "interpolate-folder-path")]
-    let folder_path = shellexpand::full(&folder_path).unwrap().to_string();
 
-    // Base relative paths on the Cargo.toml location
-    let folder_path = if Path::new(&folder_path).is_relative() {
-        Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap())
-            .join(folder_path)
-            .to_str()
-            .unwrap()
-            .to_owned()
+    let embed = if is_dynamic {
+        // Files that are in several folders are an error in release builds, so
+        // they are checked for in debug builds too.
+        if folders.len() > 1 && !allow_overlap {
+            if let (_, Some(errors)) = list_files(&folders, &config, false) {
+                return Err(errors);
+            }
+        }
+        generate_dynamic_impl(&ast.ident, &ast.vis, &config, &folders)
     } else {
-        folder_path
+        generate_embed_impl(&ast.ident, &ast.vis, &config, &folders, allow_overlap)?
     };
-
-    let config = read_attribute_config(ast);
-
-    let prefixes = find_attribute_values(ast, "prefix");
-    let prefix = if prefixes.is_empty() {
-        "".to_string()
-    } else if prefixes.len() == 1 {
-        prefixes[0].clone()
-    } else {
-        panic!("#[derive(RustEmbed)] must have at most one prefix, you supplied several");
+    let path_constants = match ast
+        .attrs
+        .iter()
+        .find(|attribute| attribute.path().is_ident("path_constants"))
+    {
+        Some(attribute) if find_bool_attribute_value(ast, "path_constants")? == Some(true) => {
+            // With a single folder, its whole prefix is left out of the names
+            // no matter where the prefix attribute is.
+            let names_prefix = match folders.as_slice() {
+                [folder] => &folder.prefix,
+                _ => &shared_prefix,
+            };
+            generate_path_constants(
+                &ast.ident,
+                &ast.vis,
+                &config,
+                &folders,
+                allow_overlap,
+                names_prefix,
+                attribute,
+            )?
+        }
+        _ => quote! {},
     };
-
-    if cfg!(debug_assertions) && !cfg!(feature = "always-embed") {
-        generate_dynamic_impl(&ast.ident, &config, &folder_path, &prefix)
-    } else {
-        generate_embed_impl(&ast.ident, &config, &folder_path, &prefix)
+    // The embed and the path constants list the files when compiling, so the
+    // crate has to be rebuilt when files are added or removed.
+    #[cfg(tracked_path)]
+    if !is_dynamic || !path_constants.is_empty() {
+        folders::track_folders(&folders);
     }
+    Ok(quote! {
+        #(#missing_folder_warnings)*
+        #embed
+        #path_constants
+    })
 }
 
 #[proc_macro_derive(
     RustEmbed,
-    attributes(folder, prefix, include, exclude, gzip, br, zstd)
+    attributes(
+        folder,
+        prefix,
+        allow_missing,
+        allow_overlap,
+        path_constants,
+        include,
+        exclude,
+        gzip,
+        br,
+        zstd,
+        deflate,
+        precompressed,
+        compress,
+        incompressible_mime_type,
+        default_incompressible_mime_types,
+        gzip_level,
+        zopfli_iterations,
+        br_quality,
+        br_window,
+        zstd_level,
+        zstd_dictionary,
+        zstd_dictionary_size,
+        compression_threshold,
+        min_compression_saving,
+        min_compression_size
+    )
 )]
 /// A folder that is embedded into your program.
 ///
@@ -105,9 +195,16 @@ fn impl_rust_embed_for_web(ast: &syn::DeriveInput) -> TokenStream2 {
 /// example will embed the files under `<your-workspace>/examples/public` into
 /// your program.
 ///
+/// In release builds, the files are embedded with `include_bytes!` so Cargo
+/// rebuilds your crate when they change. With the `nightly` feature and a
+/// nightly compiler the folders are tracked too, so adding or removing files
+/// also triggers a rebuild. Otherwise you can add a build script with
+/// `cargo:rerun-if-changed=<folder>` for that.
+///
 /// Please check the package readme for more details.
 pub fn derive_input_object(input: TokenStream) -> TokenStream {
-    let ast: DeriveInput = syn::parse(input).unwrap();
-    let gen = impl_rust_embed_for_web(&ast);
-    gen.into()
+    let ast = syn::parse_macro_input!(input as DeriveInput);
+    impl_rust_embed_for_web(&ast)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
 }
@@ -0,0 +1,73 @@
+use std::{
+    env,
+    num::NonZeroUsize,
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+/// The environment variable that can be used to limit how many threads are
+/// used to read and compress files.
+const THREADS_VAR: &str = "RUST_EMBED_FOR_WEB_THREADS";
+
+/// How many threads to use for some number of items.
+///
+/// Uses the number set in the environment if there is one, or the number of
+/// CPUs otherwise.
+fn thread_count(items: usize) -> usize {
+    let threads = env::var(THREADS_VAR)
+        .ok()
+        .and_then(|threads| threads.trim().parse::<NonZeroUsize>().ok())
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    threads.min(items)
+}
+
+/// Call `f` on all the items using a bounded number of threads.
+///
+/// The results are in the same order as the items, regardless of which thread
+/// finished first, so the generated code doesn't change between builds. If `f`
+/// panics, the panic is resumed on the calling thread.
+///
+/// `f` must not touch anything from `proc_macro`, which only works on the main
+/// thread. Build the tokens from the results instead.
+pub(crate) fn parallel_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = thread_count(items.len());
+    if threads <= 1 {
+        return items.iter().map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let (next, f) = (&next, &f);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(move || {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        match items.get(index) {
+                            Some(item) => results.push((index, f(item))),
+                            None => return results,
+                        }
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| {
+                worker
+                    .join()
+                    .unwrap_or_else(|error| panic::resume_unwind(error))
+            })
+            .collect()
+    });
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
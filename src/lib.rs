@@ -10,20 +10,34 @@
 //! If you are using this with Actix Web, there's an existing responder
 //! [`actix-web-rust-embed-responder`](https://lib.rs/crates/actix-web-rust-embed-responder)
 //! which will handle everything for you, from negotiating compressed responses
-//! to cache revalidation.
+//! to cache revalidation. If you are using axum or anything else built on
+//! tower, enable the `tower` feature and use `ServeEmbed`.
 #![forbid(unsafe_code)]
 
+use std::borrow::Cow;
+
 #[allow(unused_imports)]
 #[macro_use]
 extern crate rust_embed_for_web_impl;
 
 pub use rust_embed_for_web_impl::*;
 
-pub use rust_embed_for_web_utils::{DynamicFile, EmbedableFile, EmbeddedFile};
+pub use rust_embed_for_web_utils::{
+    negotiate_encoding, ConditionalRequest, ConditionalResponse, DirEntry, DynamicFile,
+    EmbedableFile, EmbeddedFile, Encoding, DEFAULT_ENCODING_PREFERENCE,
+};
+
+#[cfg(feature = "compression-zstd")]
+pub use rust_embed_for_web_utils::ZstdDictionary;
 
 #[doc(hidden)]
 pub extern crate rust_embed_for_web_utils as utils;
 
+#[cfg(feature = "tower")]
+mod serve;
+#[cfg(feature = "tower")]
+pub use serve::ServeEmbed;
+
 /// A folder of embedded files.
 ///
 /// The type of the file `RustEmbed::File` depends on whether we're in debug
@@ -41,4 +55,93 @@ pub trait RustEmbed {
 
     /// Get a file out of the folder.
     fn get(file_path: &str) -> Option<Self::File>;
+
+    /// Iterate over the paths of all files in the folder.
+    ///
+    /// The paths are the same ones you would pass to `get`, including the
+    /// `prefix` if one has been set.
+    fn iter() -> Filenames;
+
+    /// List the files and directories directly inside of a directory, sorted
+    /// by their paths.
+    ///
+    /// The root of the folder is `""`, and other directories are written like
+    /// `images` or `images/`. Directories only exist if there are files in
+    /// them, so this returns `None` for empty or excluded directories as well
+    /// as for paths that don't exist. A `prefix` shows up as directories
+    /// leading to the files.
+    fn read_dir(path: &str) -> Option<ReadDir>;
+
+    /// Whether a path is a directory that `read_dir` can list.
+    fn is_dir(path: &str) -> bool;
+
+    /// The zstd dictionary trained from the files, if the embed has
+    /// `#[zstd_dictionary = true]`.
+    ///
+    /// Files are only compressed with the dictionary in release builds, so this
+    /// is always `None` in debug builds.
+    #[cfg(feature = "compression-zstd")]
+    fn zstd_dictionary() -> Option<ZstdDictionary> {
+        None
+    }
+}
+
+/// An iterator over the paths of the files in an embedded folder.
+///
+/// Returned by `RustEmbed::iter`. In release builds the paths are embedded
+/// into the program, in debug builds the folder is walked when `iter` is
+/// called.
+pub enum Filenames {
+    /// Paths embedded into the program.
+    Embedded(std::slice::Iter<'static, &'static str>),
+    /// Paths found by walking the folder.
+    Dynamic(std::vec::IntoIter<String>),
+}
+
+impl Iterator for Filenames {
+    type Item = Cow<'static, str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Filenames::Embedded(names) => names.next().map(|name| Cow::Borrowed(*name)),
+            Filenames::Dynamic(names) => names.next().map(Cow::Owned),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Filenames::Embedded(names) => names.size_hint(),
+            Filenames::Dynamic(names) => names.size_hint(),
+        }
+    }
+}
+
+/// An iterator over the entries of a directory in an embedded folder.
+///
+/// Returned by `RustEmbed::read_dir`. In release builds the entries are
+/// embedded into the program, in debug builds the folder is walked when
+/// `read_dir` is called.
+pub enum ReadDir {
+    /// Entries embedded into the program.
+    Embedded(std::slice::Iter<'static, DirEntry>),
+    /// Entries found by walking the folder.
+    Dynamic(std::vec::IntoIter<DirEntry>),
+}
+
+impl Iterator for ReadDir {
+    type Item = DirEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ReadDir::Embedded(entries) => entries.next().cloned(),
+            ReadDir::Dynamic(entries) => entries.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            ReadDir::Embedded(entries) => entries.size_hint(),
+            ReadDir::Dynamic(entries) => entries.size_hint(),
+        }
+    }
 }
@@ -0,0 +1,248 @@
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    future::{ready, Ready},
+    marker::PhantomData,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use http::{
+    header::{self, HeaderMap, HeaderValue},
+    Method, Request, Response, StatusCode,
+};
+use http_body_util::Full;
+use tower_service::Service;
+
+use crate::{
+    ConditionalRequest, ConditionalResponse, EmbedableFile, Encoding, RustEmbed,
+    DEFAULT_ENCODING_PREFERENCE,
+};
+
+/// A tower `Service` that serves the files of an embed.
+///
+/// The path of the request is used to look up the file, without the leading
+/// `/`. Responses are compressed based on the `Accept-Encoding` header of the
+/// request when a compressed version of the file is available, conditional
+/// requests using `If-None-Match`, `If-Modified-Since` and friends are handled,
+/// and the `Content-Type`, `ETag` and `Last-Modified` headers are set. Missing
+/// files get a `404 Not Found`, and any method other than `GET` or `HEAD` gets
+/// a `405 Method Not Allowed`.
+///
+/// With axum, you can serve an embed under some path like this:
+///
+/// ```ignore
+/// #[derive(RustEmbed)]
+/// #[folder = "examples/public/"]
+/// struct Assets;
+///
+/// let app: Router = Router::new().nest_service("/static", ServeEmbed::<Assets>::new());
+/// ```
+pub struct ServeEmbed<T> {
+    preference: &'static [Encoding],
+    embed: PhantomData<fn() -> T>,
+}
+
+impl<T> ServeEmbed<T> {
+    pub fn new() -> Self {
+        ServeEmbed {
+            preference: &DEFAULT_ENCODING_PREFERENCE,
+            embed: PhantomData,
+        }
+    }
+
+    /// Change the order in which compressed encodings are preferred, when the
+    /// client accepts several of them equally.
+    ///
+    /// Encodings that are left out are never used. Check
+    /// `negotiate_encoding` for details.
+    pub fn encoding_preference(mut self, preference: &'static [Encoding]) -> Self {
+        self.preference = preference;
+        self
+    }
+}
+
+impl<T> Default for ServeEmbed<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for ServeEmbed<T> {
+    fn clone(&self) -> Self {
+        ServeEmbed {
+            preference: self.preference,
+            embed: PhantomData,
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for ServeEmbed<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServeEmbed")
+            .field("preference", &self.preference)
+            .finish()
+    }
+}
+
+impl<T, B> Service<Request<B>> for ServeEmbed<T>
+where
+    T: RustEmbed,
+    <T::File as EmbedableFile>::Data: Into<Bytes>,
+{
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        ready(Ok(self.respond(
+            request.method(),
+            request.uri().path(),
+            request.headers(),
+        )))
+    }
+}
+
+impl<T> ServeEmbed<T>
+where
+    T: RustEmbed,
+    <T::File as EmbedableFile>::Data: Into<Bytes>,
+{
+    fn respond(&self, method: &Method, path: &str, headers: &HeaderMap) -> Response<Full<Bytes>> {
+        if method != Method::GET && method != Method::HEAD {
+            let mut response = empty_response(StatusCode::METHOD_NOT_ALLOWED);
+            response
+                .headers_mut()
+                .insert(header::ALLOW, HeaderValue::from_static("GET, HEAD"));
+            return response;
+        }
+
+        let file = match percent_decode(path.strip_prefix('/').unwrap_or(path))
+            .and_then(|path| T::get(&path))
+        {
+            Some(file) => file,
+            None => return empty_response(StatusCode::NOT_FOUND),
+        };
+
+        let if_match = header_value(headers, header::IF_MATCH);
+        let if_none_match = header_value(headers, header::IF_NONE_MATCH);
+        let if_modified_since = header_value(headers, header::IF_MODIFIED_SINCE);
+        let if_unmodified_since = header_value(headers, header::IF_UNMODIFIED_SINCE);
+        let condition = ConditionalRequest {
+            if_match: if_match.as_deref(),
+            if_none_match: if_none_match.as_deref(),
+            if_modified_since: if_modified_since.as_deref(),
+            if_unmodified_since: if_unmodified_since.as_deref(),
+        }
+        .evaluate(&file, true);
+
+        let mut response = match condition {
+            ConditionalResponse::Ok => {
+                let accept_encoding = header_value(headers, header::ACCEPT_ENCODING);
+                match file.negotiate_data(accept_encoding.as_deref(), self.preference) {
+                    Some((encoding, data)) => {
+                        let data: Bytes = data.into();
+                        let mut response = if method == Method::HEAD {
+                            let mut response = empty_response(StatusCode::OK);
+                            response
+                                .headers_mut()
+                                .insert(header::CONTENT_LENGTH, HeaderValue::from(data.len()));
+                            response
+                        } else {
+                            Response::new(Full::new(data))
+                        };
+                        if let Some(content_encoding) = encoding.content_encoding() {
+                            response.headers_mut().insert(
+                                header::CONTENT_ENCODING,
+                                HeaderValue::from_static(content_encoding),
+                            );
+                        }
+                        if let Some(mime_type) = file.mime_type() {
+                            insert_header(&mut response, header::CONTENT_TYPE, mime_type.as_ref());
+                        }
+                        response
+                    }
+                    None => {
+                        // The response depends on the accepted encodings, so
+                        // caches must not reuse it for other clients.
+                        let mut response = empty_response(StatusCode::NOT_ACCEPTABLE);
+                        response
+                            .headers_mut()
+                            .insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+                        return response;
+                    }
+                }
+            }
+            ConditionalResponse::NotModified => empty_response(StatusCode::NOT_MODIFIED),
+            ConditionalResponse::PreconditionFailed => {
+                return empty_response(StatusCode::PRECONDITION_FAILED)
+            }
+        };
+
+        insert_header(&mut response, header::ETAG, file.etag().as_ref());
+        if let Some(last_modified) = file.last_modified() {
+            insert_header(&mut response, header::LAST_MODIFIED, last_modified.as_ref());
+        }
+        response
+            .headers_mut()
+            .insert(header::VARY, HeaderValue::from_static("accept-encoding"));
+        response
+    }
+}
+
+fn empty_response(status: StatusCode) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::default());
+    *response.status_mut() = status;
+    response
+}
+
+fn insert_header(response: &mut Response<Full<Bytes>>, name: header::HeaderName, value: &str) {
+    if let Ok(value) = HeaderValue::from_str(value) {
+        response.headers_mut().insert(name, value);
+    }
+}
+
+/// Get the value of a header, combining the values if the header is repeated.
+fn header_value(headers: &HeaderMap, name: header::HeaderName) -> Option<Cow<'_, str>> {
+    let mut values = headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok());
+    let first = values.next()?;
+    match values.next() {
+        None => Some(Cow::Borrowed(first)),
+        Some(second) => {
+            let mut combined = format!("{}, {}", first, second);
+            for value in values {
+                combined.push_str(", ");
+                combined.push_str(value);
+            }
+            Some(Cow::Owned(combined))
+        }
+    }
+}
+
+/// Decode the percent encoded characters in a request path.
+fn percent_decode(path: &str) -> Option<Cow<'_, str>> {
+    if !path.contains('%') {
+        return Some(Cow::Borrowed(path));
+    }
+
+    let hex = |digit: u8| (digit as char).to_digit(16);
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut bytes = path.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let high = hex(bytes.next()?)?;
+            let low = hex(bytes.next()?)?;
+            decoded.push((high * 16 + low) as u8);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    String::from_utf8(decoded).ok().map(Cow::Owned)
+}
@@ -0,0 +1,12 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/does-not-exist"]
+#[allow_missing = true]
+struct Embed;
+
+#[test]
+fn missing_folder_is_empty() {
+    assert!(Embed::get("index.html").is_none());
+    assert_eq!(Embed::iter().count(), 0);
+}
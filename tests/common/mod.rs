@@ -5,3 +5,7 @@ pub fn read_embed_as_string<T: EmbedableFile>(file: T) -> String {
     let index_contents = String::from_utf8_lossy(data.as_ref());
     index_contents.to_string()
 }
+
+pub fn read_embed_as_bytes<T: EmbedableFile>(file: T) -> Vec<u8> {
+    file.data().as_ref().to_vec()
+}
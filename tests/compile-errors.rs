@@ -0,0 +1,14 @@
+#[test]
+fn compile_errors() {
+    // The test cases are built in a project inside the target folder, so they
+    // find their folders through this instead of relative paths, which depend
+    // on where the target folder is.
+    std::env::set_var("RUST_EMBED_FOR_WEB_ROOT", env!("CARGO_MANIFEST_DIR"));
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile-errors/*.rs");
+    if cfg!(feature = "always-embed") {
+        t.compile_fail("tests/compile-errors/embed/*.rs");
+    } else {
+        t.compile_fail("tests/compile-errors/dynamic/*.rs");
+    }
+}
@@ -0,0 +1,8 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[br_quality = "best"]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[compress(gzip = false)]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[compression_threshold = 1.5]
+struct Embed;
+
+fn main() {}
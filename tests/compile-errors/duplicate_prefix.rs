@@ -0,0 +1,9 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+#[prefix = "public/"]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,11 @@
+use rust_embed_for_web::{EmbeddedFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "$RUST_EMBED_FOR_WEB_ROOT/examples/public"]
+struct Embed;
+
+static ROUTES: &[(&str, &EmbeddedFile)] = Embed::ENTRIES;
+
+fn main() {
+    assert!(!ROUTES.is_empty());
+}
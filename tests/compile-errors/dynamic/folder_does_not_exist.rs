@@ -0,0 +1,10 @@
+// Missing folders are only a warning in debug builds, deny it to check it.
+#![deny(deprecated)]
+
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "/this/folder/does-not-exist"]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "/this/folder/does-not-exist"]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder("examples/public")]
+struct Embed;
+
+fn main() {}
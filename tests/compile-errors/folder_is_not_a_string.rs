@@ -0,0 +1,7 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = 1]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+struct Embed;
+
+fn main() {}
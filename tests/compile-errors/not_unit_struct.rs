@@ -0,0 +1,9 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed {
+    field: u8,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "$RUST_EMBED_FOR_WEB_ROOT/tests/multiple-folders/first"]
+#[folder = "$RUST_EMBED_FOR_WEB_ROOT/tests/multiple-folders/second"]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,10 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "$RUST_EMBED_FOR_WEB_ROOT/examples/public"]
+#[path_constants = true]
+struct Embed;
+
+fn main() {
+    Embed::get(Embed::IMAGES_LAMA_PNG);
+}
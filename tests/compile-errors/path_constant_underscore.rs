@@ -0,0 +1,8 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "$RUST_EMBED_FOR_WEB_ROOT/tests/path-constants-underscore"]
+#[path_constants = true]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "$RUST_EMBED_FOR_WEB_UNSET_VARIABLE/public"]
+struct Embed;
+
+fn main() {}
@@ -0,0 +1,67 @@
+use std::io::{BufReader, Write};
+
+use flate2::write::GzDecoder;
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+// An explicit level, since embeds without one use Zopfli when it's enabled.
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[gzip_level = 1]
+struct Low;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[gzip_level = 9]
+#[br_quality = 11]
+#[br_window = 24]
+#[zstd_level = 22]
+struct Best;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[gzip_level = 0]
+#[br_quality = 0]
+#[br_window = 10]
+#[zstd_level = 1]
+struct Fastest;
+
+#[test]
+fn gzip_level_zero_is_not_worth_including() {
+    assert!(Fastest::get("index.html").unwrap().data_gzip().is_none());
+}
+
+#[test]
+fn higher_levels_compress_at_least_as_well() {
+    let low = Low::get("index.html").unwrap();
+    let best = Best::get("index.html").unwrap();
+    let fastest = Fastest::get("index.html").unwrap();
+    assert!(best.data_gzip().unwrap().len() <= low.data_gzip().unwrap().len());
+    assert!(best.data_zstd().unwrap().len() <= fastest.data_zstd().unwrap().len());
+    assert!(best.data_br().unwrap().len() <= fastest.data_br().unwrap().len());
+}
+
+#[test]
+fn gzip_roundtrip_at_best_level() {
+    let compressed = Best::get("index.html").unwrap().data_gzip().unwrap();
+    let mut decompressed: Vec<u8> = Vec::new();
+    let mut decoder = GzDecoder::new(&mut decompressed);
+    decoder.write_all(compressed).unwrap();
+    decoder.finish().unwrap();
+    assert_eq!(decompressed, Best::get("index.html").unwrap().data());
+}
+
+#[test]
+fn br_roundtrip_at_fastest_quality() {
+    let compressed = Fastest::get("index.html").unwrap().data_br().unwrap();
+    let mut decompressed: Vec<u8> = Vec::new();
+    let mut data_read = BufReader::new(compressed);
+    brotli::BrotliDecompress(&mut data_read, &mut decompressed).unwrap();
+    assert_eq!(decompressed, Fastest::get("index.html").unwrap().data());
+}
+
+#[test]
+fn zstd_roundtrip_at_best_level() {
+    let compressed = Best::get("index.html").unwrap().data_zstd().unwrap();
+    let decompressed = zstd::decode_all(compressed).unwrap();
+    assert_eq!(decompressed, Best::get("index.html").unwrap().data());
+}
@@ -0,0 +1,58 @@
+use rust_embed_for_web::{utils::Config, EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[incompressible_mime_type = "text/html"]
+struct SkipHtml;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[incompressible_mime_type = "text/*"]
+struct SkipText;
+
+#[test]
+fn added_mime_types_are_not_compressed() {
+    let file = SkipHtml::get("index.html").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+    let file = SkipHtml::get("main.css").unwrap();
+    assert!(file.data_gzip().is_some());
+    assert!(file.data_br().is_some());
+}
+
+#[test]
+fn wildcard_mime_types_are_not_compressed() {
+    assert!(SkipText::get("index.html").unwrap().data_gzip().is_none());
+    assert!(SkipText::get("main.css").unwrap().data_gzip().is_none());
+}
+
+#[test]
+fn compressed_formats_are_incompressible_by_default() {
+    let config = Config::new();
+    assert!(!config.is_compressible_mime_type(Some("image/jpeg")));
+    assert!(!config.is_compressible_mime_type(Some("font/woff2")));
+    assert!(!config.is_compressible_mime_type(Some("video/mp4")));
+    assert!(config.is_compressible_mime_type(Some("text/html")));
+    assert!(config.is_compressible_mime_type(Some("image/svg+xml")));
+    assert!(config.is_compressible_mime_type(None));
+}
+
+#[test]
+fn default_mime_types_can_be_disabled() {
+    let mut config = Config::new();
+    config.add_incompressible_mime_type("application/wasm".to_string());
+    config.set_default_incompressible_mime_types(false);
+    assert!(config.is_compressible_mime_type(Some("image/jpeg")));
+    assert!(!config.is_compressible_mime_type(Some("application/wasm")));
+    config.set_default_incompressible_mime_types(true);
+    assert!(!config.is_compressible_mime_type(Some("image/jpeg")));
+}
+
+#[test]
+fn added_mime_types_are_kept_when_defaults_are_disabled() {
+    let mut config = Config::new();
+    config.add_incompressible_mime_type("image/png".to_string());
+    config.set_default_incompressible_mime_types(false);
+    assert!(!config.is_compressible_mime_type(Some("image/png")));
+    assert!(config.is_compressible_mime_type(Some("image/jpeg")));
+}
@@ -0,0 +1,58 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+// Clippy flags the repeated `all = false` as a duplicated attribute.
+#[allow(clippy::duplicated_attributes)]
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[compress(path = "*.css", all = false)]
+#[compress(path = "*.html", all = false, br = true)]
+struct Rules;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[compress(path = "*", all = false)]
+#[compress(path = "index.html", gzip = true)]
+struct LaterRulesWin;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[br = false]
+#[compress(path = "main.css", br = true)]
+struct RulesOverrideEmbed;
+
+#[test]
+fn rules_disable_compression() {
+    let file = Rules::get("main.css").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+    assert!(file.data_zstd().is_none());
+}
+
+#[test]
+fn rules_can_enable_some_encodings() {
+    let file = Rules::get("index.html").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_some());
+    assert!(file.data_zstd().is_none());
+}
+
+#[test]
+fn later_rules_win() {
+    let file = LaterRulesWin::get("index.html").unwrap();
+    assert!(file.data_gzip().is_some());
+    assert!(file.data_br().is_none());
+    let file = LaterRulesWin::get("main.css").unwrap();
+    assert!(file.data_gzip().is_none());
+}
+
+#[test]
+fn rules_override_the_embed_settings() {
+    assert!(RulesOverrideEmbed::get("main.css")
+        .unwrap()
+        .data_br()
+        .is_some());
+    assert!(RulesOverrideEmbed::get("index.html")
+        .unwrap()
+        .data_br()
+        .is_none());
+}
@@ -0,0 +1,59 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct DefaultThreshold;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[min_compression_size = 1024]
+struct MinSize;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[min_compression_saving = 100000]
+struct MinSaving;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[compression_threshold = 0.01]
+struct StrictThreshold;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[compression_threshold = 1]
+struct LooseThreshold;
+
+#[test]
+fn small_files_are_compressed_by_default() {
+    let file = DefaultThreshold::get("main.css").unwrap();
+    assert!(file.data_gzip().is_some());
+    assert!(file.data_br().is_some());
+}
+
+#[test]
+fn files_below_min_size_are_not_compressed() {
+    let file = MinSize::get("main.css").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+    let file = MinSize::get("index.html").unwrap();
+    assert!(file.data_gzip().is_some());
+    assert!(file.data_br().is_some());
+}
+
+#[test]
+fn compression_must_save_min_bytes() {
+    let file = MinSaving::get("index.html").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+}
+
+#[test]
+fn compression_must_meet_threshold() {
+    let file = StrictThreshold::get("index.html").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+    let file = LooseThreshold::get("index.html").unwrap();
+    assert!(file.data_gzip().is_some());
+    assert!(file.data_br().is_some());
+}
@@ -55,3 +55,28 @@ fn compression_zstd_roundtrip() {
     let decompressed_body = String::from_utf8_lossy(&decompressed[..]);
     assert!(decompressed_body.starts_with("<!DOCTYPE html>"));
 }
+
+#[test]
+fn negotiate_picks_compressed_data() {
+    use rust_embed_for_web::{Encoding, DEFAULT_ENCODING_PREFERENCE};
+
+    let file = Embed::get("index.html").unwrap();
+    let (encoding, data) = file
+        .negotiate_data(Some("gzip, br"), &DEFAULT_ENCODING_PREFERENCE)
+        .unwrap();
+    assert_eq!(encoding, Encoding::Br);
+    assert_eq!(data, file.data_br().unwrap());
+
+    let (encoding, data) = file
+        .negotiate_data(Some("gzip, zstd"), &DEFAULT_ENCODING_PREFERENCE)
+        .unwrap();
+    assert_eq!(encoding, Encoding::Zstd);
+    assert_eq!(data, file.data_zstd().unwrap());
+
+    let file = Embed::get("images/flower.jpg").unwrap();
+    let (encoding, data) = file
+        .negotiate_data(Some("gzip, br, zstd"), &DEFAULT_ENCODING_PREFERENCE)
+        .unwrap();
+    assert_eq!(encoding, Encoding::Identity);
+    assert_eq!(data, file.data());
+}
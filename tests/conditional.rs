@@ -0,0 +1,178 @@
+use rust_embed_for_web::{ConditionalRequest, ConditionalResponse, DynamicFile, EmbedableFile};
+
+fn file() -> DynamicFile {
+    DynamicFile::read_from_fs("examples/public/index.html").unwrap()
+}
+
+fn http_date(timestamp: i64) -> String {
+    chrono::DateTime::from_timestamp(timestamp, 0)
+        .unwrap()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+fn evaluate(request: ConditionalRequest) -> ConditionalResponse {
+    request.evaluate(&file(), true)
+}
+
+#[test]
+fn no_conditions_is_ok() {
+    assert_eq!(
+        evaluate(ConditionalRequest::default()),
+        ConditionalResponse::Ok
+    );
+}
+
+#[test]
+fn if_none_match_with_matching_etag_is_not_modified() {
+    let etag = file().etag();
+    let list = format!("\"other\", {}", etag);
+    let weak = format!("W/{}", etag);
+    for header in [&etag, &list, &weak, &"*".to_string()] {
+        let request = ConditionalRequest {
+            if_none_match: Some(header),
+            ..Default::default()
+        };
+        assert_eq!(evaluate(request), ConditionalResponse::NotModified);
+        assert_eq!(
+            request.evaluate(&file(), false),
+            ConditionalResponse::PreconditionFailed
+        );
+    }
+}
+
+#[test]
+fn if_none_match_with_other_etag_is_ok() {
+    let request = ConditionalRequest {
+        if_none_match: Some("\"other\", W/\"another\""),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::Ok);
+}
+
+#[test]
+fn if_match_uses_strong_comparison() {
+    let etag = file().etag();
+    let request = ConditionalRequest {
+        if_match: Some(&etag),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::Ok);
+
+    let weak = format!("W/{}", etag);
+    let request = ConditionalRequest {
+        if_match: Some(&weak),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::PreconditionFailed);
+
+    let request = ConditionalRequest {
+        if_match: Some("\"other\""),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::PreconditionFailed);
+}
+
+#[test]
+fn if_modified_since() {
+    let last_modified = file().last_modified_timestamp().unwrap();
+
+    let same = http_date(last_modified);
+    let request = ConditionalRequest {
+        if_modified_since: Some(&same),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::NotModified);
+    assert_eq!(request.evaluate(&file(), false), ConditionalResponse::Ok);
+
+    let earlier = http_date(last_modified - 60);
+    let request = ConditionalRequest {
+        if_modified_since: Some(&earlier),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::Ok);
+}
+
+#[test]
+fn if_unmodified_since() {
+    let last_modified = file().last_modified_timestamp().unwrap();
+
+    let later = http_date(last_modified + 60);
+    let request = ConditionalRequest {
+        if_unmodified_since: Some(&later),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::Ok);
+
+    let earlier = http_date(last_modified - 60);
+    let request = ConditionalRequest {
+        if_unmodified_since: Some(&earlier),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::PreconditionFailed);
+}
+
+#[test]
+fn etags_take_precedence_over_dates() {
+    let file = file();
+    let etag = file.etag();
+    let last_modified = file.last_modified_timestamp().unwrap();
+    let earlier = http_date(last_modified - 60);
+    let later = http_date(last_modified + 60);
+
+    // The date alone would be a 200, but the ETag matches.
+    let request = ConditionalRequest {
+        if_none_match: Some(&etag),
+        if_modified_since: Some(&earlier),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::NotModified);
+
+    // The date alone would be a 304, but the ETag doesn't match.
+    let request = ConditionalRequest {
+        if_none_match: Some("\"other\""),
+        if_modified_since: Some(&later),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::Ok);
+
+    // The date alone would be a 412, but the ETag matches.
+    let request = ConditionalRequest {
+        if_match: Some(&etag),
+        if_unmodified_since: Some(&earlier),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::Ok);
+}
+
+#[test]
+fn obsolete_date_formats_are_supported() {
+    let request = ConditionalRequest {
+        if_unmodified_since: Some("Sunday, 06-Nov-94 08:49:37 GMT"),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::PreconditionFailed);
+
+    let request = ConditionalRequest {
+        if_unmodified_since: Some("Sun Nov  6 08:49:37 1994"),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::PreconditionFailed);
+}
+
+#[test]
+fn invalid_dates_are_ignored() {
+    let request = ConditionalRequest {
+        if_modified_since: Some("not a date"),
+        if_unmodified_since: Some("not a date"),
+        ..Default::default()
+    };
+    assert_eq!(evaluate(request), ConditionalResponse::Ok);
+}
+
+#[test]
+fn status_codes() {
+    assert_eq!(ConditionalResponse::Ok.status_code(), 200);
+    assert_eq!(ConditionalResponse::NotModified.status_code(), 304);
+    assert_eq!(ConditionalResponse::PreconditionFailed.status_code(), 412);
+}
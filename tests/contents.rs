@@ -1,6 +1,6 @@
 mod common;
 
-use common::read_embed_as_string;
+use common::{read_embed_as_bytes, read_embed_as_string};
 use rust_embed_for_web::RustEmbed;
 
 #[derive(RustEmbed)]
@@ -18,3 +18,12 @@ fn existing_file_in_folder_is_there() {
     let file = Embed::get("images/doc.txt").unwrap();
     assert_eq!(read_embed_as_string(file), "Testing 1 2 3");
 }
+
+#[test]
+fn binary_file_matches_the_file_on_disk() {
+    let file = Embed::get("images/llama.png").unwrap();
+    assert_eq!(
+        read_embed_as_bytes(file),
+        std::fs::read("examples/public/images/llama.png").unwrap()
+    );
+}
@@ -0,0 +1,68 @@
+use std::io::Write;
+
+use flate2::write::ZlibDecoder;
+use rust_embed_for_web::{EmbedableFile, Encoding, RustEmbed, DEFAULT_ENCODING_PREFERENCE};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+struct DefaultDeflate;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+#[deflate = false]
+struct FalseDeflate;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+#[deflate = true]
+struct TrueDeflate;
+
+#[test]
+fn deflate_is_used_by_default() {
+    let file = DefaultDeflate::get("index.html").unwrap();
+    assert!(file.data_deflate().is_some());
+}
+
+#[test]
+fn deflate_is_used_when_enabled() {
+    let file = TrueDeflate::get("index.html").unwrap();
+    assert!(file.data_deflate().is_some());
+}
+
+#[test]
+fn deflate_is_not_available_when_disabled() {
+    let file = FalseDeflate::get("index.html").unwrap();
+    assert!(file.data_deflate().is_none());
+}
+
+#[test]
+fn image_files_dont_get_deflate_compressed() {
+    let file = DefaultDeflate::get("images/flower.jpg").unwrap();
+    assert!(file.data_deflate().is_none());
+}
+
+#[test]
+fn compression_deflate_roundtrip() {
+    let file = DefaultDeflate::get("index.html").unwrap();
+    let mut decompressed: Vec<u8> = Vec::new();
+    let mut decoder = ZlibDecoder::new(&mut decompressed);
+    decoder.write_all(file.data_deflate().unwrap()).unwrap();
+    decoder.finish().unwrap();
+    assert_eq!(decompressed, file.data());
+}
+
+#[test]
+fn deflate_is_negotiated_when_it_is_the_only_option() {
+    let file = DefaultDeflate::get("index.html").unwrap();
+    let (encoding, data) = file
+        .negotiate_data(Some("deflate"), &DEFAULT_ENCODING_PREFERENCE)
+        .unwrap();
+    assert_eq!(encoding, Encoding::Deflate);
+    assert_eq!(encoding.content_encoding(), Some("deflate"));
+    assert_eq!(data, file.data_deflate().unwrap());
+
+    let (encoding, _) = file
+        .negotiate_data(Some("gzip, deflate"), &DEFAULT_ENCODING_PREFERENCE)
+        .unwrap();
+    assert_eq!(encoding, Encoding::Gzip);
+}
@@ -0,0 +1,94 @@
+//! These also pass with `always-embed`, since dynamic files should be
+//! compressed the same way as embedded files.
+use std::io::Read;
+
+use flate2::read::GzDecoder;
+use rust_embed_for_web::{EmbedableFile, Encoding, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[br = false]
+#[gzip_level = 9]
+#[compress(path = "*.css", all = false)]
+struct Configured;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[min_compression_size = 1000000]
+struct TooSmall;
+
+// The data is a `Vec` for dynamic files and a slice for embedded files.
+fn decompress_gzip<D: AsRef<[u8]>>(data: D) -> Vec<u8> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(data.as_ref())
+        .read_to_end(&mut decompressed)
+        .unwrap();
+    decompressed
+}
+
+fn decompress_br<D: AsRef<[u8]>>(data: D) -> Vec<u8> {
+    let mut decompressed = Vec::new();
+    brotli::BrotliDecompress(&mut data.as_ref(), &mut decompressed).unwrap();
+    decompressed
+}
+
+#[test]
+fn files_are_compressed() {
+    let file = Embed::get("index.html").unwrap();
+    assert_eq!(decompress_gzip(file.data_gzip().unwrap()), file.data());
+    assert_eq!(decompress_br(file.data_br().unwrap()), file.data());
+}
+
+#[test]
+fn compressed_files_are_reused() {
+    let first = Embed::get("index.html").unwrap();
+    let second = Embed::get("index.html").unwrap();
+    assert_eq!(first.data_br(), second.data_br());
+}
+
+#[test]
+fn config_is_used() {
+    let file = Configured::get("index.html").unwrap();
+    assert!(file.data_gzip().is_some());
+    assert!(file.data_br().is_none());
+
+    let file = Configured::get("main.css").unwrap();
+    assert!(file.data_gzip().is_none());
+}
+
+#[test]
+fn thresholds_are_used() {
+    let file = TooSmall::get("index.html").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+}
+
+#[test]
+fn compressed_formats_are_skipped() {
+    let file = Embed::get("images/flower.jpg").unwrap();
+    assert!(file.data_gzip().is_none());
+    assert!(file.data_br().is_none());
+}
+
+fn to_vec<D: AsRef<[u8]>>(data: D) -> Vec<u8> {
+    data.as_ref().to_vec()
+}
+
+fn compress_like_embedded<D: AsRef<[u8]>>(encoding: Encoding, data: D) -> Option<Vec<u8>> {
+    let config = rust_embed_for_web::utils::Config::new();
+    rust_embed_for_web::utils::compress::compress(encoding, &config, data.as_ref())
+}
+
+#[test]
+fn files_are_compressed_like_embedded_files() {
+    let file = Embed::get("index.html").unwrap();
+    for encoding in [Encoding::Gzip, Encoding::Br] {
+        let expected = compress_like_embedded(encoding, file.data());
+        let actual = file.data_encoded(encoding).map(to_vec);
+        assert_eq!(actual, expected);
+    }
+}
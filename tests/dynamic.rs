@@ -11,8 +11,9 @@ fn dynamic_file_compressed_data_is_none() {
     // In debug mode without always-embed, this should use DynamicFile
     let file = DynamicAssets::get("index.html").unwrap();
 
-    // When always-embed is not enabled, DynamicFile always returns None for compressed data
-    #[cfg(not(feature = "always-embed"))]
+    // When always-embed is not enabled, DynamicFile always returns None for
+    // compressed data unless dynamic-compression is enabled
+    #[cfg(not(any(feature = "always-embed", feature = "dynamic-compression")))]
     {
         assert!(file.data_gzip().is_none());
         assert!(file.data_br().is_none());
@@ -60,7 +61,7 @@ fn explicit_dynamic_compression_coverage() {
     let file = DynamicAssets::get("index.html").unwrap();
 
     // When always-embed is not enabled, test the DynamicFile paths
-    #[cfg(not(feature = "always-embed"))]
+    #[cfg(not(any(feature = "always-embed", feature = "dynamic-compression")))]
     {
         // Test each compression method explicitly to ensure coverage
         let gzip_result = file.data_gzip();
@@ -0,0 +1,57 @@
+use rust_embed_for_web::{EmbedableFile, EmbeddedFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "tests/missing-folder"]
+#[allow_missing = true]
+struct Empty;
+
+mod assets {
+    use rust_embed_for_web::RustEmbed;
+
+    #[derive(RustEmbed)]
+    #[folder = "examples/public"]
+    pub struct Public;
+}
+
+static ENTRIES: &[(&str, &EmbeddedFile)] = Embed::ENTRIES;
+static FIRST: &EmbeddedFile = Embed::ENTRIES[0].1;
+
+#[test]
+fn entries_can_be_used_in_statics() {
+    assert_eq!(ENTRIES.len(), 5);
+    assert_eq!(FIRST.name(), "doc.txt");
+}
+
+#[test]
+fn entries_match_iter_and_get() {
+    let names: Vec<String> = Embed::iter().map(|name| name.into_owned()).collect();
+    let paths: Vec<&str> = Embed::ENTRIES.iter().map(|(path, _)| *path).collect();
+    assert_eq!(names, paths);
+    for (path, file) in Embed::ENTRIES {
+        assert!(path.starts_with("static/"));
+        assert_eq!(Embed::get(path).unwrap().hash(), file.hash());
+    }
+}
+
+#[test]
+fn entries_are_not_copied() {
+    let (_, file) = Embed::ENTRIES[0];
+    assert!(std::ptr::eq(file, ENTRIES[0].1));
+    assert!(std::ptr::eq(file.data().as_ptr(), FIRST.data().as_ptr()));
+}
+
+#[test]
+fn entries_have_the_visibility_of_the_struct() {
+    assert_eq!(assets::Public::ENTRIES.len(), 5);
+}
+
+#[test]
+fn entries_of_an_empty_embed() {
+    assert!(Empty::ENTRIES.is_empty());
+    assert!(Empty::get("index.html").is_none());
+}
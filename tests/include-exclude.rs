@@ -21,3 +21,10 @@ fn excluded_file_is_missing() {
 fn included_overrides_the_exclude() {
     assert!(Embed::get("images/flower.jpg").is_some());
 }
+
+#[test]
+fn iter_respects_include_and_exclude() {
+    let mut names: Vec<String> = Embed::iter().map(|name| name.into_owned()).collect();
+    names.sort();
+    assert_eq!(names, vec!["images/flower.jpg", "index.html", "main.css"]);
+}
@@ -0,0 +1,60 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+struct PrefixedEmbed;
+
+fn sorted_names<T: RustEmbed>() -> Vec<String> {
+    let mut names: Vec<String> = T::iter().map(|name| name.into_owned()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn iter_lists_all_files() {
+    assert_eq!(
+        sorted_names::<Embed>(),
+        vec![
+            "images/doc.txt",
+            "images/flower.jpg",
+            "images/llama.png",
+            "index.html",
+            "main.css",
+        ]
+    );
+}
+
+#[test]
+fn iter_includes_prefix() {
+    assert_eq!(
+        sorted_names::<PrefixedEmbed>(),
+        vec![
+            "static/images/doc.txt",
+            "static/images/flower.jpg",
+            "static/images/llama.png",
+            "static/index.html",
+            "static/main.css",
+        ]
+    );
+}
+
+#[test]
+fn iter_paths_can_be_fetched() {
+    for name in Embed::iter() {
+        assert!(Embed::get(&name).is_some(), "{} is missing", name);
+    }
+    for name in PrefixedEmbed::iter() {
+        assert!(PrefixedEmbed::get(&name).is_some(), "{} is missing", name);
+    }
+}
+
+#[test]
+fn iter_order_is_stable() {
+    let names: Vec<String> = Embed::iter().map(|name| name.into_owned()).collect();
+    assert_eq!(names, sorted_names::<Embed>());
+}
@@ -0,0 +1,73 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "tests/multiple-folders/first"]
+#[folder = "tests/multiple-folders/second"]
+#[allow_overlap = true]
+struct Merged;
+
+#[derive(RustEmbed)]
+#[prefix = "static/"]
+#[folder = "tests/multiple-folders/first"]
+#[prefix = "first/"]
+#[folder = "tests/multiple-folders/second"]
+#[prefix = "second/"]
+struct Prefixed;
+
+fn text<T: EmbedableFile>(file: T) -> String {
+    String::from_utf8(file.data().as_ref().to_vec()).unwrap()
+}
+
+fn sorted_names<T: RustEmbed>() -> Vec<String> {
+    let mut names: Vec<String> = T::iter().map(|name| name.into_owned()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn files_from_all_folders_are_embedded() {
+    assert_eq!(
+        text(Merged::get("first.txt").unwrap()),
+        "only in the first folder\n"
+    );
+    assert_eq!(
+        text(Merged::get("second.txt").unwrap()),
+        "only in the second folder\n"
+    );
+}
+
+#[test]
+fn first_folder_wins_on_overlap() {
+    assert_eq!(text(Merged::get("index.html").unwrap()), "<p>first</p>\n");
+}
+
+#[test]
+fn iter_lists_overlapping_files_once() {
+    assert_eq!(
+        sorted_names::<Merged>(),
+        vec!["first.txt", "index.html", "second.txt"]
+    );
+}
+
+#[test]
+fn folders_can_have_their_own_prefix() {
+    assert_eq!(
+        text(Prefixed::get("static/first/index.html").unwrap()),
+        "<p>first</p>\n"
+    );
+    assert_eq!(
+        text(Prefixed::get("static/second/index.html").unwrap()),
+        "<p>second</p>\n"
+    );
+    assert!(Prefixed::get("static/index.html").is_none());
+    assert!(Prefixed::get("first/index.html").is_none());
+    assert_eq!(
+        sorted_names::<Prefixed>(),
+        vec![
+            "static/first/first.txt",
+            "static/first/index.html",
+            "static/second/index.html",
+            "static/second/second.txt",
+        ]
+    );
+}
@@ -0,0 +1,126 @@
+use rust_embed_for_web::{
+    negotiate_encoding, DynamicFile, EmbedableFile, Encoding, DEFAULT_ENCODING_PREFERENCE,
+};
+
+fn negotiate(accept_encoding: Option<&str>) -> Option<Encoding> {
+    negotiate_encoding(accept_encoding, &DEFAULT_ENCODING_PREFERENCE, |_| true)
+}
+
+#[test]
+fn missing_header_is_identity() {
+    assert_eq!(negotiate(None), Some(Encoding::Identity));
+}
+
+#[test]
+fn empty_header_is_identity() {
+    assert_eq!(negotiate(Some("")), Some(Encoding::Identity));
+}
+
+#[test]
+fn server_preference_breaks_ties() {
+    assert_eq!(
+        negotiate(Some("gzip, deflate, br, zstd")),
+        Some(Encoding::Br)
+    );
+    assert_eq!(
+        negotiate_encoding(
+            Some("gzip, br, zstd"),
+            &[Encoding::Zstd, Encoding::Gzip, Encoding::Br],
+            |_| true
+        ),
+        Some(Encoding::Zstd)
+    );
+}
+
+#[test]
+fn quality_values_are_respected() {
+    assert_eq!(
+        negotiate(Some("br;q=0.5, gzip;q=0.8")),
+        Some(Encoding::Gzip)
+    );
+    assert_eq!(negotiate(Some("br;q=0, gzip")), Some(Encoding::Gzip));
+    assert_eq!(
+        negotiate(Some("GZIP;Q=0.2, identity")),
+        Some(Encoding::Identity)
+    );
+}
+
+#[test]
+fn unavailable_encodings_are_skipped() {
+    assert_eq!(
+        negotiate_encoding(Some("br, gzip;q=0.5"), &DEFAULT_ENCODING_PREFERENCE, |e| {
+            e != Encoding::Br
+        }),
+        Some(Encoding::Gzip)
+    );
+    assert_eq!(
+        negotiate_encoding(Some("br"), &DEFAULT_ENCODING_PREFERENCE, |_| false),
+        Some(Encoding::Identity)
+    );
+}
+
+#[test]
+fn encodings_missing_from_preference_are_skipped() {
+    assert_eq!(
+        negotiate_encoding(Some("br"), &[Encoding::Gzip], |_| true),
+        Some(Encoding::Identity)
+    );
+}
+
+#[test]
+fn wildcard_matches_unlisted_encodings() {
+    assert_eq!(negotiate(Some("*")), Some(Encoding::Br));
+    assert_eq!(negotiate(Some("br;q=0, *;q=0.5")), Some(Encoding::Zstd));
+}
+
+#[test]
+fn identity_can_be_excluded() {
+    assert_eq!(
+        negotiate_encoding(Some("identity;q=0"), &DEFAULT_ENCODING_PREFERENCE, |_| {
+            false
+        }),
+        None
+    );
+    assert_eq!(
+        negotiate_encoding(Some("*;q=0"), &DEFAULT_ENCODING_PREFERENCE, |_| false),
+        None
+    );
+    assert_eq!(
+        negotiate_encoding(
+            Some("*;q=0, identity"),
+            &DEFAULT_ENCODING_PREFERENCE,
+            |_| false
+        ),
+        Some(Encoding::Identity)
+    );
+}
+
+#[test]
+fn malformed_entries_are_ignored() {
+    assert_eq!(
+        negotiate(Some("br;q=2, gzip;q=abc, zstd;q=0.1")),
+        Some(Encoding::Zstd)
+    );
+    assert_eq!(negotiate(Some(", ,unknown")), Some(Encoding::Identity));
+}
+
+#[test]
+fn content_encoding_tokens() {
+    assert_eq!(Encoding::Identity.content_encoding(), None);
+    assert_eq!(Encoding::Gzip.content_encoding(), Some("gzip"));
+    assert_eq!(Encoding::Br.content_encoding(), Some("br"));
+    assert_eq!(Encoding::Zstd.content_encoding(), Some("zstd"));
+}
+
+#[test]
+fn uncompressed_file_is_served_as_identity() {
+    let file = DynamicFile::read_from_fs("examples/public/index.html").unwrap();
+    let (encoding, data) = file
+        .negotiate_data(Some("gzip, br"), &DEFAULT_ENCODING_PREFERENCE)
+        .unwrap();
+    assert_eq!(encoding, Encoding::Identity);
+    assert_eq!(data, file.data());
+    assert!(file
+        .negotiate_data(Some("br, identity;q=0"), &DEFAULT_ENCODING_PREFERENCE)
+        .is_none());
+}
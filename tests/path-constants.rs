@@ -0,0 +1,32 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[path_constants = true]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/"]
+#[path_constants = true]
+struct PrefixedEmbed;
+
+#[test]
+fn constants_are_the_paths() {
+    assert_eq!(Embed::INDEX_HTML, "index.html");
+    assert_eq!(Embed::MAIN_CSS, "main.css");
+    assert_eq!(Embed::IMAGES_LLAMA_PNG, "images/llama.png");
+    assert_eq!(Embed::IMAGES_DOC_TXT, "images/doc.txt");
+}
+
+#[test]
+fn constants_can_be_used_with_get() {
+    let file = Embed::get(Embed::IMAGES_FLOWER_JPG).unwrap();
+    assert_eq!(file.name(), "flower.jpg");
+}
+
+#[test]
+fn constants_include_the_prefix() {
+    assert_eq!(PrefixedEmbed::INDEX_HTML, "static/index.html");
+    assert!(PrefixedEmbed::get(PrefixedEmbed::INDEX_HTML).is_some());
+}
@@ -0,0 +1,65 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/images"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/images"]
+#[prefix = "static/"]
+struct PrefixedEmbed;
+
+#[test]
+fn parent_directory_is_rejected() {
+    assert!(Embed::get("../index.html").is_none());
+    assert!(Embed::get("../../Cargo.toml").is_none());
+    assert!(PrefixedEmbed::get("static/../index.html").is_none());
+}
+
+#[test]
+fn parent_directory_inside_folder_is_rejected() {
+    assert!(Embed::get("doc.txt").is_some());
+    assert!(Embed::get("../images/doc.txt").is_none());
+}
+
+#[test]
+fn non_canonical_paths_are_rejected() {
+    assert!(Embed::get("./doc.txt").is_none());
+    assert!(Embed::get("/doc.txt").is_none());
+    assert!(Embed::get("doc.txt/").is_none());
+    assert!(PrefixedEmbed::get("static//doc.txt").is_none());
+}
+
+#[test]
+fn absolute_paths_are_rejected() {
+    let absolute = std::fs::canonicalize("examples/public/index.html").unwrap();
+    assert!(Embed::get(absolute.to_str().unwrap()).is_none());
+}
+
+#[test]
+fn folders_are_rejected() {
+    assert!(Embed::get("").is_none());
+    assert!(PrefixedEmbed::get("static/").is_none());
+}
+
+#[cfg(unix)]
+#[test]
+fn symlinks_escaping_the_folder_are_rejected() {
+    use rust_embed_for_web::utils::find_file;
+
+    let root = std::env::temp_dir().join(format!("rust-embed-for-web-{}", std::process::id()));
+    let folder = root.join("folder");
+    std::fs::create_dir_all(&folder).unwrap();
+    std::fs::write(root.join("secret.txt"), "secret").unwrap();
+    std::fs::write(folder.join("public.txt"), "public").unwrap();
+    std::os::unix::fs::symlink(root.join("secret.txt"), folder.join("escape.txt")).unwrap();
+    std::os::unix::fs::symlink(folder.join("public.txt"), folder.join("inside.txt")).unwrap();
+
+    let folder = folder.to_str().unwrap();
+    assert!(find_file(folder, "public.txt").is_some());
+    assert!(find_file(folder, "inside.txt").is_some());
+    assert!(find_file(folder, "escape.txt").is_none());
+    assert!(find_file(folder, "../secret.txt").is_none());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1 @@
+console.log("This is the original file, which does not match the gzipped one.");
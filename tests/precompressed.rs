@@ -0,0 +1,126 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/precompressed/"]
+#[precompressed = true]
+struct Precompressed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/precompressed/"]
+struct NotPrecompressed;
+
+fn sorted_names<T: RustEmbed>() -> Vec<String> {
+    let mut names: Vec<String> = T::iter().map(|name| name.into_owned()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn precompressed_files_are_hidden() {
+    assert!(Precompressed::get("app.js").is_some());
+    assert!(Precompressed::get("app.js.gz").is_none());
+    assert!(Precompressed::get("app.js.br").is_none());
+    let mut expected = vec!["app.js", "notes.txt", "standalone.txt.gz"];
+    // Without zstd support, zstd files are just regular files.
+    if !cfg!(feature = "compression-zstd") {
+        expected.insert(1, "app.js.zst");
+    }
+    assert_eq!(sorted_names::<Precompressed>(), expected);
+}
+
+#[test]
+fn files_without_originals_are_not_hidden() {
+    assert!(Precompressed::get("standalone.txt.gz").is_some());
+}
+
+#[test]
+fn precompressed_files_are_regular_files_by_default() {
+    assert!(NotPrecompressed::get("app.js.gz").is_some());
+    assert!(NotPrecompressed::get("app.js.br").is_some());
+    assert_eq!(sorted_names::<NotPrecompressed>().len(), 6);
+}
+
+// Dynamic files are served the precompressed files too, so these pass in both
+// modes.
+mod used {
+    use super::*;
+    use rust_embed_for_web::EmbedableFile;
+
+    #[cfg(feature = "include-exclude")]
+    #[derive(RustEmbed)]
+    #[folder = "examples/precompressed/"]
+    #[precompressed = true]
+    #[exclude = "*.br"]
+    struct WithoutBr;
+
+    #[derive(RustEmbed)]
+    #[folder = "examples/precompressed/"]
+    #[precompressed = true]
+    #[br = false]
+    struct BrDisabled;
+
+    fn read(path: &str) -> Vec<u8> {
+        std::fs::read(format!("examples/precompressed/{}", path)).unwrap()
+    }
+
+    // The data is a `Vec` for dynamic files and a slice for embedded files.
+    fn to_vec<D: AsRef<[u8]>>(data: D) -> Vec<u8> {
+        data.as_ref().to_vec()
+    }
+
+    #[test]
+    fn precompressed_files_are_used() {
+        let file = Precompressed::get("app.js").unwrap();
+        assert_eq!(to_vec(file.data()), read("app.js"));
+        assert_eq!(to_vec(file.data_gzip().unwrap()), read("app.js.gz"));
+        assert_eq!(to_vec(file.data_br().unwrap()), read("app.js.br"));
+        #[cfg(feature = "compression-zstd")]
+        assert_eq!(to_vec(file.data_zstd().unwrap()), read("app.js.zst"));
+    }
+
+    #[cfg(feature = "include-exclude")]
+    #[test]
+    fn excluded_precompressed_files_are_not_used() {
+        let file = WithoutBr::get("app.js").unwrap();
+        assert_eq!(to_vec(file.data_gzip().unwrap()), read("app.js.gz"));
+        assert_ne!(file.data_br().map(to_vec), Some(read("app.js.br")));
+        assert!(WithoutBr::get("app.js.br").is_none());
+    }
+
+    #[test]
+    fn precompressed_files_follow_the_compression_settings() {
+        let file = BrDisabled::get("app.js").unwrap();
+        assert!(file.data_br().is_none());
+        assert_eq!(to_vec(file.data_gzip().unwrap()), read("app.js.gz"));
+        assert!(BrDisabled::get("app.js.br").is_none());
+    }
+
+    #[cfg(any(feature = "always-embed", feature = "dynamic-compression"))]
+    #[test]
+    fn files_are_compressed_when_not_precompressed() {
+        let file = NotPrecompressed::get("app.js").unwrap();
+        assert_ne!(to_vec(file.data_gzip().unwrap()), read("app.js.gz"));
+    }
+}
+
+// Precompressed files that don't match their originals are a compile error in
+// release builds, see `tests/compile-errors`.
+#[cfg(all(debug_assertions, not(feature = "always-embed")))]
+mod mismatch {
+    use super::*;
+    use rust_embed_for_web::EmbedableFile;
+
+    #[derive(RustEmbed)]
+    #[folder = "tests/precompressed-mismatch/"]
+    #[precompressed = true]
+    struct Mismatch;
+
+    #[test]
+    fn mismatched_precompressed_files_are_regular_files() {
+        assert!(Mismatch::get("index.js.gz").is_some());
+        assert_eq!(sorted_names::<Mismatch>(), vec!["index.js", "index.js.gz"]);
+        let file = Mismatch::get("index.js").unwrap();
+        let precompressed = std::fs::read("tests/precompressed-mismatch/index.js.gz").unwrap();
+        assert_ne!(file.data_gzip(), Some(precompressed));
+    }
+}
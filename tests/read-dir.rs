@@ -0,0 +1,81 @@
+use rust_embed_for_web::RustEmbed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[prefix = "static/assets/"]
+struct PrefixedEmbed;
+
+fn list<T: RustEmbed>(path: &str) -> Option<Vec<(String, bool)>> {
+    Some(
+        T::read_dir(path)?
+            .map(|entry| (entry.path().to_owned(), entry.is_dir()))
+            .collect(),
+    )
+}
+
+#[test]
+fn read_dir_lists_the_root() {
+    assert_eq!(
+        list::<Embed>("").unwrap(),
+        vec![
+            ("images".to_owned(), true),
+            ("index.html".to_owned(), false),
+            ("main.css".to_owned(), false),
+        ]
+    );
+}
+
+#[test]
+fn read_dir_lists_subdirectories() {
+    let expected = vec![
+        ("images/doc.txt".to_owned(), false),
+        ("images/flower.jpg".to_owned(), false),
+        ("images/llama.png".to_owned(), false),
+    ];
+    assert_eq!(list::<Embed>("images").unwrap(), expected);
+    assert_eq!(list::<Embed>("images/").unwrap(), expected);
+}
+
+#[test]
+fn read_dir_entries_have_names() {
+    let names: Vec<String> = Embed::read_dir("images")
+        .unwrap()
+        .map(|entry| entry.name().to_owned())
+        .collect();
+    assert_eq!(names, vec!["doc.txt", "flower.jpg", "llama.png"]);
+}
+
+#[test]
+fn read_dir_is_none_for_files_and_missing_paths() {
+    assert!(Embed::read_dir("index.html").is_none());
+    assert!(Embed::read_dir("missing").is_none());
+    assert!(Embed::read_dir("/images").is_none());
+}
+
+#[test]
+fn is_dir_works() {
+    assert!(Embed::is_dir(""));
+    assert!(Embed::is_dir("images"));
+    assert!(Embed::is_dir("images/"));
+    assert!(!Embed::is_dir("index.html"));
+    assert!(!Embed::is_dir("images/flower.jpg"));
+    assert!(!Embed::is_dir("missing"));
+}
+
+#[test]
+fn prefix_shows_up_as_directories() {
+    assert_eq!(
+        list::<PrefixedEmbed>("").unwrap(),
+        vec![("static".to_owned(), true)]
+    );
+    assert_eq!(
+        list::<PrefixedEmbed>("static").unwrap(),
+        vec![("static/assets".to_owned(), true)]
+    );
+    assert!(PrefixedEmbed::is_dir("static/assets/images"));
+    assert!(PrefixedEmbed::get("static/assets/images/doc.txt").is_some());
+}
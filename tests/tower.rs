@@ -0,0 +1,192 @@
+use axum::Router;
+use http::{header, Method, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use rust_embed_for_web::{EmbedableFile, Encoding, RustEmbed, ServeEmbed};
+use tower::ServiceExt;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public/"]
+struct Embed;
+
+async fn send(request: Request<()>) -> Response<Full<bytes::Bytes>> {
+    ServeEmbed::<Embed>::new().oneshot(request).await.unwrap()
+}
+
+async fn body(response: Response<Full<bytes::Bytes>>) -> Vec<u8> {
+    response
+        .into_body()
+        .collect()
+        .await
+        .unwrap()
+        .to_bytes()
+        .to_vec()
+}
+
+fn bytes<D: AsRef<[u8]>>(data: D) -> Vec<u8> {
+    data.as_ref().to_vec()
+}
+
+fn text<M: AsRef<str>>(meta: M) -> String {
+    meta.as_ref().to_string()
+}
+
+fn get(path: &str) -> http::request::Builder {
+    Request::builder().uri(path)
+}
+
+#[tokio::test]
+async fn serves_files() {
+    let file = Embed::get("index.html").unwrap();
+    let response = send(get("/index.html").body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "text/html");
+    assert_eq!(response.headers()[header::ETAG], text(file.etag()));
+    assert_eq!(
+        response.headers()[header::LAST_MODIFIED],
+        text(file.last_modified().unwrap())
+    );
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+    assert_eq!(body(response).await, bytes(file.data()));
+}
+
+#[tokio::test]
+async fn serves_files_in_folders() {
+    let response = send(get("/images/doc.txt").body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body(response).await, b"Testing 1 2 3");
+}
+
+#[tokio::test]
+async fn missing_files_are_not_found() {
+    let response = send(get("/does-not-exist").body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = send(get("/../Cargo.toml").body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn other_methods_are_not_allowed() {
+    let response = send(get("/index.html").method(Method::POST).body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(response.headers()[header::ALLOW], "GET, HEAD");
+}
+
+#[tokio::test]
+async fn head_has_no_body() {
+    let file = Embed::get("index.html").unwrap();
+    let response = send(get("/index.html").method(Method::HEAD).body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[header::CONTENT_LENGTH],
+        bytes(file.data()).len().to_string()
+    );
+    assert!(body(response).await.is_empty());
+}
+
+#[tokio::test]
+async fn matching_etag_is_not_modified() {
+    let file = Embed::get("index.html").unwrap();
+    let response = send(
+        get("/index.html")
+            .header(header::IF_NONE_MATCH, text(file.etag()))
+            .body(())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[header::ETAG], text(file.etag()));
+    assert!(body(response).await.is_empty());
+}
+
+#[tokio::test]
+async fn failed_precondition() {
+    let response = send(
+        get("/index.html")
+            .header(header::IF_MATCH, "\"other\"")
+            .body(())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+}
+
+#[tokio::test]
+async fn compressed_responses_are_negotiated() {
+    let file = Embed::get("index.html").unwrap();
+    let response = send(
+        get("/index.html")
+            .header(header::ACCEPT_ENCODING, "gzip, br")
+            .body(())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::VARY], "accept-encoding");
+    match file.data_br() {
+        Some(data_br) => {
+            assert_eq!(response.headers()[header::CONTENT_ENCODING], "br");
+            assert_eq!(body(response).await, bytes(data_br));
+        }
+        // Dynamic files are not compressed
+        None => {
+            assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+            assert_eq!(body(response).await, bytes(file.data()));
+        }
+    }
+}
+
+#[tokio::test]
+async fn encoding_preference_can_be_changed() {
+    let file = Embed::get("index.html").unwrap();
+    let response = ServeEmbed::<Embed>::new()
+        .encoding_preference(&[Encoding::Gzip])
+        .oneshot(
+            get("/index.html")
+                .header(header::ACCEPT_ENCODING, "gzip, br")
+                .body(())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    if file.data_gzip().is_some() {
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "gzip");
+    }
+}
+
+#[tokio::test]
+async fn unacceptable_encoding() {
+    let response = send(
+        get("/images/flower.jpg")
+            .header(header::ACCEPT_ENCODING, "br, identity;q=0")
+            .body(())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+    assert_eq!(response.headers()["vary"], "accept-encoding");
+}
+
+#[tokio::test]
+async fn percent_encoded_paths_are_decoded() {
+    let response = send(get("/images/doc%2Etxt").body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = send(get("/images/doc%2").body(()).unwrap()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn works_with_axum() {
+    let app: Router = Router::new().nest_service("/static", ServeEmbed::<Embed>::new());
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/static/images/doc.txt")
+                .body(axum::body::Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"Testing 1 2 3");
+}
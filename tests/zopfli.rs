@@ -0,0 +1,41 @@
+use std::io::Write;
+
+use flate2::{
+    write::{GzDecoder, GzEncoder},
+    Compression,
+};
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+struct Embed;
+
+#[derive(RustEmbed)]
+#[folder = "examples/public"]
+#[zopfli_iterations = 1]
+struct OneIteration;
+
+fn decompress_gzip(compressed: &[u8]) -> Vec<u8> {
+    let mut decompressed: Vec<u8> = Vec::new();
+    let mut decoder = GzDecoder::new(&mut decompressed);
+    decoder.write_all(compressed).unwrap();
+    decoder.finish().unwrap();
+    decompressed
+}
+
+#[test]
+fn zopfli_gzip_roundtrip() {
+    let file = Embed::get("index.html").unwrap();
+    assert_eq!(decompress_gzip(file.data_gzip().unwrap()), file.data());
+    let file = OneIteration::get("index.html").unwrap();
+    assert_eq!(decompress_gzip(file.data_gzip().unwrap()), file.data());
+}
+
+#[test]
+fn zopfli_is_smaller_than_flate2() {
+    let file = Embed::get("index.html").unwrap();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(file.data()).unwrap();
+    let flate2 = encoder.finish().unwrap();
+    assert!(file.data_gzip().unwrap().len() < flate2.len());
+}
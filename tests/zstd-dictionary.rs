@@ -0,0 +1,68 @@
+use rust_embed_for_web::{EmbedableFile, RustEmbed};
+use sha2::{Digest, Sha256};
+
+#[derive(RustEmbed)]
+#[folder = "tests/zstd-dictionary"]
+#[zstd_dictionary = true]
+#[zstd_dictionary_size = 4096]
+struct Fragments;
+
+#[derive(RustEmbed)]
+#[folder = "tests/zstd-dictionary"]
+struct NoDictionary;
+
+fn decompress_dcz(data: &[u8], dictionary: &[u8]) -> Vec<u8> {
+    assert_eq!(
+        &data[..8],
+        &[0x5e, 0x2a, 0x4d, 0x18, 0x20, 0x00, 0x00, 0x00]
+    );
+    assert_eq!(&data[8..40], &Sha256::digest(dictionary)[..]);
+    let mut decompressor = zstd::bulk::Decompressor::with_dictionary(dictionary).unwrap();
+    decompressor.decompress(&data[40..], 1 << 20).unwrap()
+}
+
+#[test]
+fn dictionary_is_trained() {
+    let dictionary = Fragments::zstd_dictionary().unwrap();
+    assert!(!dictionary.data().is_empty());
+    assert!(dictionary.data().len() <= 4096);
+    assert!(<Fragments as RustEmbed>::zstd_dictionary().is_some());
+}
+
+#[test]
+fn no_dictionary_by_default() {
+    assert!(<NoDictionary as RustEmbed>::zstd_dictionary().is_none());
+    let file = NoDictionary::get("user-00.json").unwrap();
+    assert!(file.data_dcz().is_none());
+    assert!(file.data_zstd().is_some());
+}
+
+#[test]
+fn dictionary_hash_is_the_available_dictionary_header() {
+    let dictionary = Fragments::zstd_dictionary().unwrap();
+    let hash = dictionary.hash();
+    assert!(hash.starts_with(':') && hash.ends_with(':'));
+    // 32 bytes of SHA-256 in base64, with padding
+    assert_eq!(hash.len(), 46);
+    assert!(dictionary.is_available(Some(hash)));
+    assert!(!dictionary.is_available(Some(":AAAA:")));
+    assert!(!dictionary.is_available(None));
+}
+
+#[test]
+fn dcz_decompresses_to_the_file() {
+    let dictionary = Fragments::zstd_dictionary().unwrap();
+    for name in Fragments::iter() {
+        let file = Fragments::get(&name).unwrap();
+        let data_dcz = file.data_dcz().unwrap();
+        assert_eq!(decompress_dcz(data_dcz, dictionary.data()), file.data());
+    }
+}
+
+#[test]
+fn dcz_is_smaller_than_zstd() {
+    let file = Fragments::get("user-07.json").unwrap();
+    let data_dcz = file.data_dcz().unwrap();
+    let data_zstd = file.data_zstd().unwrap();
+    assert!(data_dcz.len() < data_zstd.len());
+}
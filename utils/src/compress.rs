@@ -0,0 +1,169 @@
+use std::io::{BufReader, Read, Write};
+#[cfg(all(feature = "compression-zopfli", feature = "zopfli"))]
+use std::num::NonZeroU64;
+
+use brotli::enc::BrotliEncoderParams;
+use flate2::{write::GzEncoder, Compression};
+#[cfg(all(feature = "compression-zstd", feature = "zstd"))]
+use zstd::stream::write::Encoder as ZstdEncoder;
+
+use crate::{Config, Encoding};
+
+pub fn compress_gzip(data: &[u8], level: u32) -> Vec<u8> {
+    let mut data_gzip: Vec<u8> = Vec::new();
+    let mut encoder = GzEncoder::new(&mut data_gzip, Compression::new(level));
+    encoder
+        .write_all(data)
+        .expect("Failed to compress gzip data");
+    encoder
+        .finish()
+        .expect("Failed to finish compression of gzip data");
+    data_gzip
+}
+
+/// Compresses data into a gzip stream using Zopfli, which is much slower than
+/// flate2 but produces smaller files.
+#[cfg(all(feature = "compression-zopfli", feature = "zopfli"))]
+pub fn compress_gzip_zopfli(data: &[u8], iterations: u64) -> Vec<u8> {
+    let mut data_gzip: Vec<u8> = Vec::new();
+    let options = zopfli::Options {
+        iteration_count: NonZeroU64::new(iterations).expect("Zopfli iterations must not be 0"),
+        ..Default::default()
+    };
+    zopfli::compress(options, zopfli::Format::Gzip, data, &mut data_gzip)
+        .expect("Failed to compress gzip data with Zopfli");
+    data_gzip
+}
+
+/// Compresses data for the `deflate` content encoding, which is the zlib
+/// format.
+#[cfg(feature = "compression-deflate")]
+pub fn compress_deflate(data: &[u8], level: u32) -> Vec<u8> {
+    use flate2::write::ZlibEncoder;
+
+    let mut data_deflate: Vec<u8> = Vec::new();
+    let mut encoder = ZlibEncoder::new(&mut data_deflate, Compression::new(level));
+    encoder
+        .write_all(data)
+        .expect("Failed to compress deflate data");
+    encoder
+        .finish()
+        .expect("Failed to finish compression of deflate data");
+    data_deflate
+}
+
+pub fn compress_br(data: &[u8], quality: u32, window: u32) -> Vec<u8> {
+    let mut data_read = BufReader::new(data);
+    let mut data_br: Vec<u8> = Vec::new();
+    let params = BrotliEncoderParams {
+        quality: quality as i32,
+        lgwin: window as i32,
+        ..Default::default()
+    };
+    brotli::BrotliCompress(&mut data_read, &mut data_br, &params)
+        .expect("Failed to compress br data");
+    data_br
+}
+
+/// Compresses data using zstd compression.
+#[cfg(all(feature = "compression-zstd", feature = "zstd"))]
+pub fn compress_zstd(data: &[u8], level: i32) -> Vec<u8> {
+    let mut data_zstd: Vec<u8> = Vec::new();
+    let mut encoder =
+        ZstdEncoder::new(&mut data_zstd, level).expect("Failed to create zstd encoder");
+    encoder
+        .write_all(data)
+        .expect("Failed to compress zstd data");
+    encoder
+        .finish()
+        .expect("Failed to finish compression of zstd data");
+    data_zstd
+}
+
+/// Whether gzip files are compressed with Zopfli. Zopfli doesn't have levels,
+/// so flate2 is used if the embed sets one.
+#[cfg(all(feature = "compression-zopfli", feature = "zopfli"))]
+fn use_zopfli(config: &Config) -> bool {
+    !config.is_gzip_level_set()
+}
+
+/// The settings that a file is compressed with for an encoding, to tell apart
+/// versions of the same file compressed with different settings in caches.
+///
+/// Returns `None` if the encoding isn't enabled by the features of the crate.
+pub fn compression_settings(encoding: Encoding, config: &Config) -> Option<String> {
+    match encoding {
+        Encoding::Identity => None,
+        Encoding::Gzip => {
+            #[cfg(all(feature = "compression-zopfli", feature = "zopfli"))]
+            if use_zopfli(config) {
+                return Some(format!("zopfli,iterations={}", config.zopfli_iterations()));
+            }
+            Some(format!("level={}", config.gzip_level()))
+        }
+        Encoding::Br => Some(format!(
+            "quality={},window={}",
+            config.br_quality(),
+            config.br_window()
+        )),
+        #[cfg(all(feature = "compression-zstd", feature = "zstd"))]
+        Encoding::Zstd => Some(format!("level={}", config.zstd_level())),
+        #[cfg(feature = "compression-deflate")]
+        Encoding::Deflate => Some(format!("level={}", config.gzip_level())),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+/// Compress data with an encoding using the settings of the config, which is
+/// done the same way for embedded files and dynamic files.
+///
+/// Returns `None` if the encoding isn't enabled by the features of the crate.
+pub fn compress(encoding: Encoding, config: &Config, data: &[u8]) -> Option<Vec<u8>> {
+    match encoding {
+        Encoding::Identity => None,
+        Encoding::Gzip => {
+            #[cfg(all(feature = "compression-zopfli", feature = "zopfli"))]
+            if use_zopfli(config) {
+                return Some(compress_gzip_zopfli(data, config.zopfli_iterations()));
+            }
+            Some(compress_gzip(data, config.gzip_level()))
+        }
+        Encoding::Br => Some(compress_br(data, config.br_quality(), config.br_window())),
+        #[cfg(all(feature = "compression-zstd", feature = "zstd"))]
+        Encoding::Zstd => Some(compress_zstd(data, config.zstd_level())),
+        #[cfg(feature = "compression-deflate")]
+        Encoding::Deflate => Some(compress_deflate(data, config.gzip_level())),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+/// Decompress data that was compressed with an encoding, for example to check
+/// that a precompressed file matches its original.
+pub fn decompress(encoding: Encoding, data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut decompressed: Vec<u8> = Vec::new();
+    match encoding {
+        Encoding::Identity => decompressed.extend_from_slice(data),
+        Encoding::Gzip => {
+            flate2::read::MultiGzDecoder::new(data).read_to_end(&mut decompressed)?;
+        }
+        Encoding::Br => {
+            brotli::BrotliDecompress(&mut BufReader::new(data), &mut decompressed)?;
+        }
+        #[cfg(all(feature = "compression-zstd", feature = "zstd"))]
+        Encoding::Zstd => decompressed = zstd::decode_all(data)?,
+        #[cfg(feature = "compression-deflate")]
+        Encoding::Deflate => {
+            flate2::read::ZlibDecoder::new(data).read_to_end(&mut decompressed)?;
+        }
+        #[allow(unreachable_patterns)]
+        encoding => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                format!("{} is not supported", encoding.as_str()),
+            ))
+        }
+    }
+    Ok(decompressed)
+}
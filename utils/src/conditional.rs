@@ -0,0 +1,174 @@
+use chrono::{DateTime, NaiveDateTime};
+
+use crate::EmbedableFile;
+
+/// The conditional headers of a request.
+///
+/// Fill in the values of the headers that were present in the request, then
+/// use `evaluate` to decide how to respond. For example:
+///
+/// ```
+/// # use rust_embed_for_web_utils::{ConditionalRequest, ConditionalResponse, EmbedableFile};
+/// fn respond<T: EmbedableFile>(file: T, if_none_match: Option<&str>) {
+///     let request = ConditionalRequest {
+///         if_none_match,
+///         ..Default::default()
+///     };
+///     match request.evaluate(&file, true) {
+///         ConditionalResponse::Ok => println!("200 OK"),
+///         ConditionalResponse::NotModified => println!("304 Not Modified"),
+///         ConditionalResponse::PreconditionFailed => println!("412 Precondition Failed"),
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConditionalRequest<'h> {
+    /// The value of the `If-Match` header.
+    pub if_match: Option<&'h str>,
+    /// The value of the `If-None-Match` header.
+    pub if_none_match: Option<&'h str>,
+    /// The value of the `If-Modified-Since` header.
+    pub if_modified_since: Option<&'h str>,
+    /// The value of the `If-Unmodified-Since` header.
+    pub if_unmodified_since: Option<&'h str>,
+}
+
+/// How to respond to a conditional request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConditionalResponse {
+    /// The preconditions passed, respond with the file as usual.
+    Ok,
+    /// The client already has the file, respond with `304 Not Modified`.
+    NotModified,
+    /// The preconditions failed, respond with `412 Precondition Failed`.
+    PreconditionFailed,
+}
+
+impl ConditionalResponse {
+    /// The HTTP status code for this response.
+    pub fn status_code(&self) -> u16 {
+        match self {
+            ConditionalResponse::Ok => 200,
+            ConditionalResponse::NotModified => 304,
+            ConditionalResponse::PreconditionFailed => 412,
+        }
+    }
+}
+
+impl<'h> ConditionalRequest<'h> {
+    /// Decide how to respond to the request, following the precedence rules
+    /// from RFC 9110 section 13.2.2.
+    ///
+    /// The ETag headers take precedence over the date headers: if
+    /// `If-Match` is present then `If-Unmodified-Since` is ignored, and if
+    /// `If-None-Match` is present then `If-Modified-Since` is ignored.
+    /// `If-Match` uses the strong comparison, while `If-None-Match` uses the
+    /// weak comparison. Date headers that fail to parse are ignored.
+    ///
+    /// `is_get_or_head` should be `true` if the request method is `GET` or
+    /// `HEAD`. For other methods, `If-None-Match` fails with
+    /// `PreconditionFailed` instead of `NotModified`, and `If-Modified-Since`
+    /// is ignored.
+    pub fn evaluate<T: EmbedableFile>(
+        &self,
+        file: &T,
+        is_get_or_head: bool,
+    ) -> ConditionalResponse {
+        let etag = file.etag();
+        let etag = etag.as_ref();
+        let last_modified = file.last_modified_timestamp();
+
+        if let Some(if_match) = self.if_match {
+            if !etag_list_matches(if_match, etag, true) {
+                return ConditionalResponse::PreconditionFailed;
+            }
+        } else if let (Some(if_unmodified_since), Some(last_modified)) = (
+            self.if_unmodified_since.and_then(parse_http_date),
+            last_modified,
+        ) {
+            if last_modified > if_unmodified_since {
+                return ConditionalResponse::PreconditionFailed;
+            }
+        }
+
+        if let Some(if_none_match) = self.if_none_match {
+            if etag_list_matches(if_none_match, etag, false) {
+                return if is_get_or_head {
+                    ConditionalResponse::NotModified
+                } else {
+                    ConditionalResponse::PreconditionFailed
+                };
+            }
+        } else if is_get_or_head {
+            if let (Some(if_modified_since), Some(last_modified)) = (
+                self.if_modified_since.and_then(parse_http_date),
+                last_modified,
+            ) {
+                if last_modified <= if_modified_since {
+                    return ConditionalResponse::NotModified;
+                }
+            }
+        }
+
+        ConditionalResponse::Ok
+    }
+}
+
+/// Split an entity tag into whether it is weak, and its opaque tag including
+/// the quotes.
+fn split_etag(etag: &str) -> (bool, &str) {
+    match etag.strip_prefix("W/") {
+        Some(opaque) => (true, opaque),
+        None => (false, etag),
+    }
+}
+
+/// Check if any of the entity tags in an `If-Match` or `If-None-Match` header
+/// matches the ETag of the file.
+fn etag_list_matches(header: &str, etag: &str, strong: bool) -> bool {
+    if header.trim() == "*" {
+        return true;
+    }
+    let (etag_weak, etag_opaque) = split_etag(etag);
+
+    let mut rest = header;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        if rest.is_empty() {
+            return false;
+        }
+        let (weak, opaque) = split_etag(rest);
+        // The opaque tag is a quoted string, which can't contain quotes.
+        let end = match opaque.strip_prefix('"').and_then(|v| v.find('"')) {
+            Some(end) => end + 2,
+            // Malformed entity tag, nothing after this can be parsed reliably.
+            None => return false,
+        };
+        let (opaque, remaining) = opaque.split_at(end);
+        rest = remaining;
+
+        let matches = if strong {
+            !weak && !etag_weak && opaque == etag_opaque
+        } else {
+            opaque == etag_opaque
+        };
+        if matches {
+            return true;
+        }
+    }
+}
+
+/// Parse an HTTP date into a UNIX timestamp.
+///
+/// Supports the preferred IMF-fixdate format, as well as the obsolete RFC 850
+/// and asctime formats.
+fn parse_http_date(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc2822(value) {
+        return Some(date.timestamp());
+    }
+    NaiveDateTime::parse_from_str(value, "%A, %d-%b-%y %H:%M:%S GMT")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%a %b %e %H:%M:%S %Y"))
+        .ok()
+        .map(|date| date.and_utc().timestamp())
+}
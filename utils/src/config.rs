@@ -1,15 +1,77 @@
 #[cfg(feature = "include-exclude")]
 use globset::{Glob, GlobMatcher};
 
+use crate::Encoding;
+
+/// MIME types of formats that are already compressed, which are not worth
+/// compressing again. Types ending with `/*` match any subtype.
+pub const DEFAULT_INCOMPRESSIBLE_MIME_TYPES: &[&str] = &[
+    "image/jpeg",
+    "image/png",
+    "image/gif",
+    "image/webp",
+    "image/avif",
+    "image/heic",
+    "image/jxl",
+    "video/*",
+    "audio/aac",
+    "audio/flac",
+    "audio/mp4",
+    "audio/mpeg",
+    "audio/ogg",
+    "audio/webm",
+    "font/woff",
+    "font/woff2",
+    "application/font-woff",
+    "application/gzip",
+    "application/x-gzip",
+    "application/zip",
+    "application/zstd",
+    "application/x-bzip2",
+    "application/x-xz",
+    "application/x-7z-compressed",
+    "application/x-compressed",
+    "application/vnd.rar",
+    "application/x-rar-compressed",
+    "application/java-archive",
+];
+
+/// Compression settings for the files matching a glob pattern.
+#[cfg(feature = "include-exclude")]
+#[derive(Debug)]
+struct CompressionRule {
+    matcher: GlobMatcher,
+    encodings: Vec<(Encoding, bool)>,
+}
+
 #[derive(Debug)]
 pub struct Config {
     #[cfg(feature = "include-exclude")]
     include: Vec<GlobMatcher>,
     #[cfg(feature = "include-exclude")]
     exclude: Vec<GlobMatcher>,
+    #[cfg(feature = "include-exclude")]
+    compression_rules: Vec<CompressionRule>,
     gzip: bool,
     br: bool,
     zstd: bool,
+    deflate: bool,
+    precompressed: bool,
+    zstd_dictionary: bool,
+    zstd_dictionary_size: usize,
+    /// Whether `DEFAULT_INCOMPRESSIBLE_MIME_TYPES` are skipped, kept apart
+    /// from the types added with `add_incompressible_mime_type`.
+    default_incompressible_mime_types: bool,
+    incompressible_mime_types: Vec<String>,
+    /// `None` if the level hasn't been set, so Zopfli can be used instead.
+    gzip_level: Option<u32>,
+    zopfli_iterations: u64,
+    br_quality: u32,
+    br_window: u32,
+    zstd_level: i32,
+    compression_threshold: f64,
+    min_compression_saving: u64,
+    min_compression_size: u64,
 }
 
 impl Default for Config {
@@ -19,12 +81,31 @@ impl Default for Config {
             include: vec![],
             #[cfg(feature = "include-exclude")]
             exclude: vec![],
+            #[cfg(feature = "include-exclude")]
+            compression_rules: vec![],
             gzip: true,
             br: true,
             #[cfg(feature = "compression-zstd")]
             zstd: true,
             #[cfg(not(feature = "compression-zstd"))]
             zstd: false,
+            #[cfg(feature = "compression-deflate")]
+            deflate: true,
+            #[cfg(not(feature = "compression-deflate"))]
+            deflate: false,
+            precompressed: false,
+            zstd_dictionary: false,
+            zstd_dictionary_size: 112640,
+            default_incompressible_mime_types: true,
+            incompressible_mime_types: vec![],
+            gzip_level: None,
+            zopfli_iterations: 15,
+            br_quality: 11,
+            br_window: 22,
+            zstd_level: 3,
+            compression_threshold: 0.95,
+            min_compression_saving: 0,
+            min_compression_size: 0,
         }
     }
 }
@@ -35,22 +116,40 @@ impl Config {
     }
 
     // Builder functions
+    /// Add a glob pattern for files to include.
+    ///
+    /// Returns an error if the pattern is not a valid glob.
     #[cfg(feature = "include-exclude")]
-    pub fn add_include(&mut self, pattern: String) {
-        self.include.push(
-            Glob::new(&pattern)
-                .expect("Failed to parse glob pattern for include")
-                .compile_matcher(),
-        );
+    pub fn add_include(&mut self, pattern: String) -> Result<(), globset::Error> {
+        self.include.push(Glob::new(&pattern)?.compile_matcher());
+        Ok(())
     }
 
+    /// Add a glob pattern for files to exclude.
+    ///
+    /// Returns an error if the pattern is not a valid glob.
+    #[cfg(feature = "include-exclude")]
+    pub fn add_exclude(&mut self, pattern: String) -> Result<(), globset::Error> {
+        self.exclude.push(Glob::new(&pattern)?.compile_matcher());
+        Ok(())
+    }
+
+    /// Add a rule to enable or disable some encodings for the files matching a
+    /// glob pattern, overriding the settings for the whole embed.
+    ///
+    /// When several rules match a file, the rule added last wins for each
+    /// encoding it sets. Returns an error if the pattern is not a valid glob.
     #[cfg(feature = "include-exclude")]
-    pub fn add_exclude(&mut self, pattern: String) {
-        self.exclude.push(
-            Glob::new(&pattern)
-                .expect("Failed to parse glob pattern for exclude")
-                .compile_matcher(),
-        );
+    pub fn add_compression_rule(
+        &mut self,
+        pattern: String,
+        encodings: Vec<(Encoding, bool)>,
+    ) -> Result<(), globset::Error> {
+        self.compression_rules.push(CompressionRule {
+            matcher: Glob::new(&pattern)?.compile_matcher(),
+            encodings,
+        });
+        Ok(())
     }
 
     pub fn set_gzip(&mut self, status: bool) {
@@ -66,11 +165,119 @@ impl Config {
         self.zstd = status;
     }
 
+    /// Enable or disable deflate compression for embedded files.
+    pub fn set_deflate(&mut self, status: bool) {
+        self.deflate = status;
+    }
+
+    /// Use precompressed files from the folder, like `app.js.br` next to
+    /// `app.js`, as the compressed versions of the original files instead of
+    /// compressing them. The precompressed files are not embedded on their own.
+    pub fn set_precompressed(&mut self, status: bool) {
+        self.precompressed = status;
+    }
+
+    /// Don't compress files with this MIME type, in addition to the already
+    /// compressed formats in `DEFAULT_INCOMPRESSIBLE_MIME_TYPES`. The type can
+    /// end with `/*` to match any subtype, like `video/*`.
+    pub fn add_incompressible_mime_type(&mut self, mime_type: String) {
+        self.incompressible_mime_types.push(mime_type);
+    }
+
+    /// Enable or disable skipping the formats in
+    /// `DEFAULT_INCOMPRESSIBLE_MIME_TYPES`. Types that were added with
+    /// `add_incompressible_mime_type` are always skipped.
+    pub fn set_default_incompressible_mime_types(&mut self, status: bool) {
+        self.default_incompressible_mime_types = status;
+    }
+
+    /// Train a zstd dictionary from the files of the embed, and compress the
+    /// files with it for the `dcz` encoding. This needs the compression-zstd
+    /// feature.
+    pub fn set_zstd_dictionary(&mut self, status: bool) {
+        self.zstd_dictionary = status;
+    }
+
+    /// Set the maximum size of the trained zstd dictionary in bytes. Defaults
+    /// to 110 KiB.
+    pub fn set_zstd_dictionary_size(&mut self, size: usize) {
+        self.zstd_dictionary_size = size;
+    }
+
+    /// Set the gzip compression level, from 0 (no compression) to 9 (best
+    /// compression). This is also used for deflate. Defaults to 6.
+    ///
+    /// Setting a level makes gzip files use flate2 at that level even with the
+    /// `compression-zopfli` feature, since Zopfli doesn't have levels.
+    pub fn set_gzip_level(&mut self, level: u32) {
+        self.gzip_level = Some(level);
+    }
+
+    /// Set how many iterations Zopfli runs when compressing gzip files with the
+    /// `compression-zopfli` feature. More iterations can make the files
+    /// slightly smaller but take longer. Defaults to 15.
+    pub fn set_zopfli_iterations(&mut self, iterations: u64) {
+        self.zopfli_iterations = iterations;
+    }
+
+    /// Set the brotli compression quality, from 0 (fastest) to 11 (best
+    /// compression). Defaults to 11.
+    pub fn set_br_quality(&mut self, quality: u32) {
+        self.br_quality = quality;
+    }
+
+    /// Set the brotli window size as a power of 2, from 10 to 24. Larger
+    /// windows can compress large files better, but clients need more memory
+    /// to decompress them. Defaults to 22.
+    pub fn set_br_window(&mut self, window: u32) {
+        self.br_window = window;
+    }
+
+    /// Set the zstd compression level, from 1 (fastest) to 22 (best
+    /// compression). Defaults to 3.
+    pub fn set_zstd_level(&mut self, level: i32) {
+        self.zstd_level = level;
+    }
+
+    /// Only include a compressed version of a file if its size is less than
+    /// this ratio of the original size. Defaults to 0.95, so compression has to
+    /// save at least 5%.
+    pub fn set_compression_threshold(&mut self, threshold: f64) {
+        self.compression_threshold = threshold;
+    }
+
+    /// Only include a compressed version of a file if it is at least this many
+    /// bytes smaller than the original. Defaults to 0.
+    pub fn set_min_compression_saving(&mut self, bytes: u64) {
+        self.min_compression_saving = bytes;
+    }
+
+    /// Don't compress files that are smaller than this many bytes. Defaults
+    /// to 0.
+    pub fn set_min_compression_size(&mut self, bytes: u64) {
+        self.min_compression_size = bytes;
+    }
+
     #[cfg(feature = "include-exclude")]
     pub fn get_includes(&self) -> &Vec<GlobMatcher> {
         &self.include
     }
 
+    /// The glob patterns of the compression rules, along with the encodings
+    /// each of them enables or disables, in the order they were added.
+    #[cfg(feature = "include-exclude")]
+    pub fn get_compression_rules(&self) -> impl Iterator<Item = (&str, &[(Encoding, bool)])> {
+        self.compression_rules
+            .iter()
+            .map(|rule| (rule.matcher.glob().glob(), rule.encodings.as_slice()))
+    }
+
+    /// The types that were added with `add_incompressible_mime_type`, without
+    /// the defaults.
+    pub fn get_incompressible_mime_types(&self) -> &[String] {
+        &self.incompressible_mime_types
+    }
+
     #[cfg(feature = "include-exclude")]
     pub fn get_excludes(&self) -> &Vec<GlobMatcher> {
         &self.exclude
@@ -124,4 +331,151 @@ impl Config {
             false
         }
     }
+
+    /// Check if deflate compression should be used for embedded files.
+    ///
+    /// Returns `false` when the compression-deflate feature is not enabled,
+    /// even if the config value is set to `true`.
+    pub fn should_deflate(&self) -> bool {
+        #[cfg(feature = "compression-deflate")]
+        {
+            self.deflate
+        }
+        #[cfg(not(feature = "compression-deflate"))]
+        {
+            false
+        }
+    }
+
+    /// Check if a file at some path should be compressed with an encoding,
+    /// taking the compression rules into account.
+    ///
+    /// Always returns `false` for encodings whose feature is not enabled.
+    #[allow(unused_variables)]
+    pub fn should_compress_with(&self, path: &str, encoding: Encoding) -> bool {
+        let enabled = match encoding {
+            Encoding::Identity => return false,
+            Encoding::Gzip => self.should_gzip(),
+            Encoding::Br => self.should_br(),
+            Encoding::Zstd if !cfg!(feature = "compression-zstd") => return false,
+            Encoding::Zstd => self.should_zstd(),
+            Encoding::Deflate if !cfg!(feature = "compression-deflate") => return false,
+            Encoding::Deflate => self.should_deflate(),
+        };
+        #[cfg(feature = "include-exclude")]
+        {
+            self.compression_rules
+                .iter()
+                .rev()
+                .filter(|rule| rule.matcher.is_match(path))
+                .find_map(|rule| {
+                    rule.encodings
+                        .iter()
+                        .find(|(rule_encoding, _)| *rule_encoding == encoding)
+                        .map(|(_, enabled)| *enabled)
+                })
+                .unwrap_or(enabled)
+        }
+        #[cfg(not(feature = "include-exclude"))]
+        {
+            enabled
+        }
+    }
+
+    /// Check if a file with this MIME type might be worth compressing, or if it
+    /// is in a format that is already compressed.
+    ///
+    /// Files with an unknown MIME type are always worth trying.
+    pub fn is_compressible_mime_type(&self, mime_type: Option<&str>) -> bool {
+        let mime_type = match mime_type {
+            Some(mime_type) => mime_type.split(';').next().unwrap_or("").trim(),
+            None => return true,
+        };
+        let defaults: &[&str] = if self.default_incompressible_mime_types {
+            DEFAULT_INCOMPRESSIBLE_MIME_TYPES
+        } else {
+            &[]
+        };
+        let mut incompressible_mime_types = defaults
+            .iter()
+            .copied()
+            .chain(self.incompressible_mime_types.iter().map(String::as_str));
+        !incompressible_mime_types.any(|incompressible| match incompressible.strip_suffix("/*") {
+            Some(top_level) => mime_type
+                .split('/')
+                .next()
+                .unwrap_or("")
+                .eq_ignore_ascii_case(top_level),
+            None => incompressible.eq_ignore_ascii_case(mime_type),
+        })
+    }
+
+    /// Check if a zstd dictionary should be trained from the files.
+    ///
+    /// Returns `false` when the compression-zstd feature is not enabled,
+    /// even if the config value is set to `true`.
+    pub fn should_train_zstd_dictionary(&self) -> bool {
+        cfg!(feature = "compression-zstd") && self.zstd_dictionary
+    }
+
+    pub fn zstd_dictionary_size(&self) -> usize {
+        self.zstd_dictionary_size
+    }
+
+    pub fn use_default_incompressible_mime_types(&self) -> bool {
+        self.default_incompressible_mime_types
+    }
+
+    pub fn use_precompressed(&self) -> bool {
+        self.precompressed
+    }
+
+    pub fn gzip_level(&self) -> u32 {
+        self.gzip_level.unwrap_or(6)
+    }
+
+    /// Whether the gzip level has been set, rather than being the default.
+    pub fn is_gzip_level_set(&self) -> bool {
+        self.gzip_level.is_some()
+    }
+
+    pub fn zopfli_iterations(&self) -> u64 {
+        self.zopfli_iterations
+    }
+
+    pub fn br_quality(&self) -> u32 {
+        self.br_quality
+    }
+
+    pub fn br_window(&self) -> u32 {
+        self.br_window
+    }
+
+    pub fn zstd_level(&self) -> i32 {
+        self.zstd_level
+    }
+
+    pub fn compression_threshold(&self) -> f64 {
+        self.compression_threshold
+    }
+
+    pub fn min_compression_saving(&self) -> u64 {
+        self.min_compression_saving
+    }
+
+    pub fn min_compression_size(&self) -> u64 {
+        self.min_compression_size
+    }
+
+    /// Check if a file of this size should be compressed at all.
+    pub fn should_compress(&self, original_len: u64) -> bool {
+        original_len >= self.min_compression_size
+    }
+
+    /// Check if the compressed version of a file is small enough compared to
+    /// the original to be worth including.
+    pub fn is_worth_including(&self, original_len: u64, compressed_len: u64) -> bool {
+        compressed_len < ((original_len as f64) * self.compression_threshold) as u64
+            && original_len.saturating_sub(compressed_len) >= self.min_compression_saving
+    }
 }
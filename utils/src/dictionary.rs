@@ -0,0 +1,40 @@
+/// A zstd dictionary trained from the files of an embed.
+///
+/// Browsers that support Compression Dictionary Transport can use this to
+/// decompress files sent with the `dcz` content encoding, which are often much
+/// smaller than plain zstd for small files that look alike. Serve the
+/// dictionary with a `Use-As-Dictionary` header, then for requests that send
+/// an `Available-Dictionary` header matching it and accept `dcz`, you can
+/// respond with `EmbedableFile::data_dcz`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZstdDictionary {
+    data: &'static [u8],
+    hash: &'static str,
+}
+
+impl ZstdDictionary {
+    #[doc(hidden)]
+    /// This is used internally in derived code to create the dictionary.
+    /// You don't want to manually use this function!
+    pub fn __internal_make(data: &'static [u8], hash: &'static str) -> ZstdDictionary {
+        ZstdDictionary { data, hash }
+    }
+
+    /// The contents of the dictionary.
+    pub fn data(&self) -> &'static [u8] {
+        self.data
+    }
+
+    /// The SHA-256 hash of the dictionary, base64 encoded and wrapped with
+    /// colons. This is the format browsers use for the `Available-Dictionary`
+    /// header.
+    pub fn hash(&self) -> &'static str {
+        self.hash
+    }
+
+    /// Check if the `Available-Dictionary` header of a request refers to this
+    /// dictionary.
+    pub fn is_available(&self, available_dictionary: Option<&str>) -> bool {
+        available_dictionary.map(str::trim) == Some(self.hash)
+    }
+}
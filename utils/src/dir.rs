@@ -0,0 +1,96 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+};
+
+/// A file or directory inside of a directory of an embed.
+///
+/// Directories aren't embedded themselves, they are made up from the paths of
+/// the files. A directory exists if there's a file somewhere inside of it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DirEntry {
+    path: Cow<'static, str>,
+    is_dir: bool,
+}
+
+impl DirEntry {
+    #[doc(hidden)]
+    /// This is used internally in derived code to create embedded entries.
+    /// You don't want to manually use this function!
+    pub const fn __internal_make(path: &'static str, is_dir: bool) -> DirEntry {
+        DirEntry {
+            path: Cow::Borrowed(path),
+            is_dir,
+        }
+    }
+
+    /// The full path of the entry, like `images/logo.png`.
+    ///
+    /// For files, this is the path you would pass to `get`. For directories,
+    /// it's the path you would pass to `read_dir`, without a trailing slash.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The last component of the path, like `logo.png`.
+    pub fn name(&self) -> &str {
+        match self.path.rsplit_once('/') {
+            Some((_, name)) => name,
+            None => &self.path,
+        }
+    }
+
+    /// Whether the entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
+
+    /// Whether the entry is a file.
+    pub fn is_file(&self) -> bool {
+        !self.is_dir
+    }
+}
+
+/// Get the path of a directory the way it's stored in a directory tree, which
+/// is without the trailing slash. The root directory is the empty string.
+pub fn directory_key(path: &str) -> &str {
+    path.strip_suffix('/').unwrap_or(path)
+}
+
+/// Find all the directories that the files are in, along with the files and
+/// directories directly inside each of them.
+///
+/// The root directory is the empty string, and it's always included even if
+/// there are no files. The entries of each directory are sorted by their
+/// paths.
+pub fn directory_tree<I, S>(paths: I) -> BTreeMap<String, Vec<DirEntry>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut tree: BTreeMap<String, BTreeSet<DirEntry>> = BTreeMap::new();
+    tree.insert(String::new(), BTreeSet::new());
+    for path in paths {
+        let mut path = path.as_ref();
+        let mut is_dir = false;
+        // Walk up from the file, adding each directory to its parent until
+        // reaching one that was already added by another file.
+        loop {
+            let parent = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+            let entry = DirEntry {
+                path: Cow::Owned(path.to_owned()),
+                is_dir,
+            };
+            let parent_known = tree.contains_key(parent);
+            tree.entry(parent.to_owned()).or_default().insert(entry);
+            if parent_known || parent.is_empty() {
+                break;
+            }
+            path = parent;
+            is_dir = true;
+        }
+    }
+    tree.into_iter()
+        .map(|(path, entries)| (path, entries.into_iter().collect()))
+        .collect()
+}
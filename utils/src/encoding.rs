@@ -0,0 +1,204 @@
+/// A content encoding that an embedded file can be served with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// The uncompressed file.
+    Identity,
+    Gzip,
+    Br,
+    Zstd,
+    Deflate,
+}
+
+/// The default order of preference between the compressed encodings.
+///
+/// When a client accepts several encodings equally, the one that comes first
+/// here is picked. Brotli and zstd both typically produce smaller files than
+/// gzip, and brotli is more widely supported than zstd. Deflate is only used
+/// for clients that support nothing better.
+pub const DEFAULT_ENCODING_PREFERENCE: [Encoding; 4] = [
+    Encoding::Br,
+    Encoding::Zstd,
+    Encoding::Gzip,
+    Encoding::Deflate,
+];
+
+impl Encoding {
+    /// The token for this encoding, as used in `Accept-Encoding` and
+    /// `Content-Encoding` headers.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Encoding::Identity => "identity",
+            Encoding::Gzip => "gzip",
+            Encoding::Br => "br",
+            Encoding::Zstd => "zstd",
+            Encoding::Deflate => "deflate",
+        }
+    }
+
+    /// The value to use for the `Content-Encoding` header when serving a file
+    /// with this encoding.
+    ///
+    /// This is `None` for `Identity`, in which case you should not send a
+    /// `Content-Encoding` header at all.
+    pub fn content_encoding(&self) -> Option<&'static str> {
+        match self {
+            Encoding::Identity => None,
+            encoding => Some(encoding.as_str()),
+        }
+    }
+
+    fn from_token(token: &str) -> Option<Encoding> {
+        if token.eq_ignore_ascii_case("identity") {
+            Some(Encoding::Identity)
+        } else if token.eq_ignore_ascii_case("gzip") || token.eq_ignore_ascii_case("x-gzip") {
+            Some(Encoding::Gzip)
+        } else if token.eq_ignore_ascii_case("br") {
+            Some(Encoding::Br)
+        } else if token.eq_ignore_ascii_case("zstd") {
+            Some(Encoding::Zstd)
+        } else if token.eq_ignore_ascii_case("deflate") {
+            Some(Encoding::Deflate)
+        } else {
+            None
+        }
+    }
+}
+
+/// The quality values a client assigned to the encodings, parsed from an
+/// `Accept-Encoding` header.
+struct AcceptEncoding {
+    /// The quality for each encoding that was explicitly listed.
+    listed: Vec<(Encoding, f32)>,
+    /// The quality for `*`, if it was listed.
+    wildcard: Option<f32>,
+}
+
+impl AcceptEncoding {
+    fn parse(header: &str) -> AcceptEncoding {
+        let mut listed: Vec<(Encoding, f32)> = Vec::new();
+        let mut wildcard = None;
+
+        for item in header.split(',') {
+            let mut parts = item.split(';');
+            let token = parts.next().unwrap_or("").trim();
+            if token.is_empty() {
+                continue;
+            }
+            let mut quality = Some(1.0);
+            for param in parts {
+                let mut param = param.splitn(2, '=');
+                let name = param.next().unwrap_or("").trim();
+                if name.eq_ignore_ascii_case("q") {
+                    quality = param.next().and_then(parse_quality);
+                }
+            }
+            // Entries with a malformed quality value are ignored entirely.
+            let quality = match quality {
+                Some(quality) => quality,
+                None => continue,
+            };
+
+            if token == "*" {
+                wildcard = Some(quality);
+            } else if let Some(encoding) = Encoding::from_token(token) {
+                listed.push((encoding, quality));
+            }
+        }
+
+        AcceptEncoding { listed, wildcard }
+    }
+
+    /// The quality the client assigned to an encoding, or `None` if the
+    /// encoding is not acceptable at all.
+    ///
+    /// `Identity` is acceptable unless it is excluded explicitly. When it is
+    /// only acceptable implicitly, it gets the lowest possible quality so that
+    /// any acceptable compressed encoding is picked over it.
+    fn quality(&self, encoding: Encoding) -> Option<f32> {
+        let quality = self
+            .listed
+            .iter()
+            .find(|(listed, _)| *listed == encoding)
+            .map(|(_, quality)| *quality)
+            .or(self.wildcard);
+        match (encoding, quality) {
+            (_, Some(quality)) if quality > 0.0 => Some(quality),
+            (_, Some(_)) => None,
+            (Encoding::Identity, None) => Some(0.0),
+            (_, None) => None,
+        }
+    }
+}
+
+fn parse_quality(value: &str) -> Option<f32> {
+    let value: f32 = value.trim().parse().ok()?;
+    if (0.0..=1.0).contains(&value) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+fn is_better(best: Option<(Encoding, f32)>, quality: f32) -> bool {
+    match best {
+        Some((_, best_quality)) => quality > best_quality,
+        None => true,
+    }
+}
+
+/// Pick the encoding to serve a file with, based on the `Accept-Encoding`
+/// header of the request.
+///
+/// `preference` is the order in which the server prefers the compressed
+/// encodings, which is used to break ties when the client accepts several of
+/// them with the same quality. Compressed encodings that are not listed in
+/// `preference` are never picked. `is_available` should return whether the
+/// file is available in an encoding, `Identity` is always assumed to be
+/// available.
+///
+/// If the request had no `Accept-Encoding` header, pass `None` and the file
+/// will be served uncompressed. If the client does not accept any encoding
+/// that is available, this returns `None` and you should respond with a
+/// `406 Not Acceptable` (or ignore the header and serve the uncompressed
+/// file).
+///
+/// ```
+/// # use rust_embed_for_web_utils::{negotiate_encoding, Encoding, DEFAULT_ENCODING_PREFERENCE};
+/// let encoding = negotiate_encoding(
+///     Some("gzip, br;q=0.9"),
+///     &DEFAULT_ENCODING_PREFERENCE,
+///     |_| true,
+/// );
+/// assert_eq!(encoding, Some(Encoding::Gzip));
+/// ```
+pub fn negotiate_encoding<F: Fn(Encoding) -> bool>(
+    accept_encoding: Option<&str>,
+    preference: &[Encoding],
+    is_available: F,
+) -> Option<Encoding> {
+    let accept = match accept_encoding {
+        Some(header) => AcceptEncoding::parse(header),
+        None => return Some(Encoding::Identity),
+    };
+
+    let mut best: Option<(Encoding, f32)> = None;
+    for encoding in preference {
+        if *encoding == Encoding::Identity || !is_available(*encoding) {
+            continue;
+        }
+        if let Some(quality) = accept.quality(*encoding) {
+            if is_better(best, quality) {
+                best = Some((*encoding, quality));
+            }
+        }
+    }
+    // Identity only wins if the client prefers it strictly over the
+    // compressed encodings.
+    if let Some(quality) = accept.quality(Encoding::Identity) {
+        if is_better(best, quality) {
+            best = Some((Encoding::Identity, quality));
+        }
+    }
+
+    best.map(|(encoding, _)| encoding)
+}
@@ -1,3 +1,5 @@
+use crate::encoding::{negotiate_encoding, Encoding};
+
 /// An embedable file.
 ///
 /// The file is embedded into the program for release builds, and dynamically
@@ -52,6 +54,33 @@ pub trait EmbedableFile {
     fn data_zstd(&self) -> Option<Self::Data> {
         None
     }
+    /// The contents of the file, compressed with the zstd dictionary of the
+    /// embed in the `dcz` format.
+    ///
+    /// This is `Some` if `#[zstd_dictionary = true]` is set and the file
+    /// benefits from compression, and it's always `None` for dynamic files.
+    /// Only send it to clients whose `Available-Dictionary` header matches the
+    /// dictionary, check `ZstdDictionary` for details.
+    fn data_dcz(&self) -> Option<Self::Data> {
+        None
+    }
+    /// The contents of the file, compressed for the `deflate` encoding.
+    ///
+    /// This is the zlib format, as the `deflate` content encoding is defined
+    /// in RFC 9110. This is `Some` if precompression has been done. `None` if
+    /// the file was not precompressed, either because the file doesn't benefit
+    /// from compression or because deflate was disabled with
+    /// `#[deflate = false]`.
+    #[cfg(feature = "compression-deflate")]
+    fn data_deflate(&self) -> Option<Self::Data>;
+
+    /// The contents of the file, compressed for the `deflate` encoding.
+    ///
+    /// Always returns `None` when the compression-deflate feature is disabled.
+    #[cfg(not(feature = "compression-deflate"))]
+    fn data_deflate(&self) -> Option<Self::Data> {
+        None
+    }
     /// The UNIX timestamp of when the file was last modified.
     fn last_modified_timestamp(&self) -> Option<i64>;
     /// The rfc2822 encoded last modified date. This is the format you use for
@@ -65,4 +94,52 @@ pub trait EmbedableFile {
     /// The mime type for the file, if one can be guessed from the file
     /// extension.
     fn mime_type(&self) -> Option<Self::Meta>;
+
+    /// The contents of the file in some encoding.
+    ///
+    /// This is `None` if the file is not available in that encoding, the
+    /// contents are always available with `Encoding::Identity`.
+    fn data_encoded(&self, encoding: Encoding) -> Option<Self::Data> {
+        match encoding {
+            Encoding::Identity => Some(self.data()),
+            Encoding::Gzip => self.data_gzip(),
+            Encoding::Br => self.data_br(),
+            Encoding::Zstd => self.data_zstd(),
+            Encoding::Deflate => self.data_deflate(),
+        }
+    }
+
+    /// Pick the best contents of the file to send, based on the
+    /// `Accept-Encoding` header of a request.
+    ///
+    /// Returns the encoding that was picked along with the contents in that
+    /// encoding. Use `Encoding::content_encoding` to get the value for the
+    /// `Content-Encoding` header. Check `negotiate_encoding` for the details of
+    /// how the encoding is picked, including what `preference` does and when
+    /// this returns `None`.
+    ///
+    /// ```
+    /// # use rust_embed_for_web_utils::{EmbedableFile, DEFAULT_ENCODING_PREFERENCE};
+    /// fn respond<T: EmbedableFile>(file: T, accept_encoding: Option<&str>) {
+    ///     match file.negotiate_data(accept_encoding, &DEFAULT_ENCODING_PREFERENCE) {
+    ///         Some((encoding, data)) => {
+    ///             if let Some(content_encoding) = encoding.content_encoding() {
+    ///                 println!("Content-Encoding: {}", content_encoding);
+    ///             }
+    ///             println!("Sending {} bytes", data.as_ref().len());
+    ///         }
+    ///         None => println!("406 Not Acceptable"),
+    ///     }
+    /// }
+    /// ```
+    fn negotiate_data(
+        &self,
+        accept_encoding: Option<&str>,
+        preference: &[Encoding],
+    ) -> Option<(Encoding, Self::Data)> {
+        let encoding = negotiate_encoding(accept_encoding, preference, |encoding| {
+            self.data_encoded(encoding).is_some()
+        })?;
+        self.data_encoded(encoding).map(|data| (encoding, data))
+    }
 }
@@ -0,0 +1,72 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use crate::{
+    compress::{compress, compression_settings},
+    Config, Encoding,
+};
+
+/// Compressed versions of files, keyed by the path of the file, the encoding,
+/// and the compression settings, along with the hash of the file they were
+/// compressed from.
+///
+/// Compressing with the highest settings is slow, so this keeps the files
+/// from being compressed again on every request as long as they don't change.
+/// When a file changes, its entry is replaced, so the cache doesn't grow while
+/// files are edited.
+type Cache = Mutex<HashMap<(PathBuf, Encoding, String), (String, Arc<Vec<u8>>)>>;
+
+fn cache() -> &'static Cache {
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Compress a file with an encoding, reusing the compressed version from the
+/// cache if the file has been compressed with the same settings before.
+///
+/// Like in release builds, returns `None` if the compressed version is not
+/// worth including.
+pub(crate) fn compress_cached(
+    path: &Path,
+    hash: &str,
+    data: &[u8],
+    encoding: Encoding,
+    config: &Config,
+) -> Option<Vec<u8>> {
+    let len = data.len() as u64;
+    if !config.should_compress(len) {
+        return None;
+    }
+    // There are no settings if the encoding isn't enabled.
+    let key = (
+        path.to_owned(),
+        encoding,
+        compression_settings(encoding, config)?,
+    );
+    let cached = match cache().lock().unwrap().get(&key) {
+        Some((cached_hash, compressed)) if cached_hash == hash => Some(compressed.clone()),
+        _ => None,
+    };
+    // The lock isn't held while compressing, so other files can be served in
+    // the meantime. Two requests compressing the same file both get the same
+    // result.
+    let compressed = match cached {
+        Some(compressed) => compressed,
+        None => {
+            let compressed = Arc::new(compress(encoding, config, data)?);
+            cache()
+                .lock()
+                .unwrap()
+                .insert(key, (hash.to_owned(), compressed.clone()));
+            compressed
+        }
+    };
+    if config.is_worth_including(len, compressed.len() as u64) {
+        Some(compressed.as_ref().clone())
+    } else {
+        None
+    }
+}
@@ -11,6 +11,9 @@ use new_mime_guess::MimeGuess;
 use sha2::{Digest, Sha256};
 
 use super::common::EmbedableFile;
+#[cfg(feature = "dynamic-compression")]
+use crate::Config;
+use crate::Encoding;
 
 /// A file read from the file system dynamically.
 ///
@@ -19,6 +22,9 @@ use super::common::EmbedableFile;
 ///
 /// You should interface with this object using the `EmbedableFile` trait, which
 /// is implemented for both the embedded and dynamic files.
+///
+/// Dynamic files are not compressed, unless the `dynamic-compression` feature
+/// is enabled or the embed uses precompressed files.
 #[derive(Clone)]
 pub struct DynamicFile {
     name: String,
@@ -26,6 +32,10 @@ pub struct DynamicFile {
     hash: String,
     last_modified_timestamp: Option<i64>,
     mime_type: Option<String>,
+    /// Where the file was read from, to cache the compressed versions by.
+    #[cfg(feature = "dynamic-compression")]
+    path: std::path::PathBuf,
+    compressed: Vec<(Encoding, Vec<u8>)>,
 }
 
 impl EmbedableFile for DynamicFile {
@@ -41,16 +51,21 @@ impl EmbedableFile for DynamicFile {
     }
 
     fn data_gzip(&self) -> Option<Self::Data> {
-        None
+        self.compressed(Encoding::Gzip)
     }
 
     fn data_br(&self) -> Option<Self::Data> {
-        None
+        self.compressed(Encoding::Br)
     }
 
     #[cfg(feature = "compression-zstd")]
     fn data_zstd(&self) -> Option<Self::Data> {
-        None
+        self.compressed(Encoding::Zstd)
+    }
+
+    #[cfg(feature = "compression-deflate")]
+    fn data_deflate(&self) -> Option<Self::Data> {
+        self.compressed(Encoding::Deflate)
     }
 
     fn last_modified(&self) -> Option<Self::Meta> {
@@ -116,8 +131,57 @@ impl DynamicFile {
             hash,
             last_modified_timestamp,
             mime_type,
+            #[cfg(feature = "dynamic-compression")]
+            path: path.as_ref().to_owned(),
+            compressed: Vec::new(),
         })
     }
+
+    /// Compress the file with the encodings that the config enables for it,
+    /// the same way it would be compressed in a release build.
+    ///
+    /// `rel_path` is the path of the file in the embed, including the prefix,
+    /// which the compression rules are matched against. The compressed
+    /// versions are cached for each file, so this is fast unless the file has
+    /// changed since the last time it was compressed.
+    ///
+    /// Encodings that the file already has a precompressed version for are
+    /// skipped.
+    #[cfg(feature = "dynamic-compression")]
+    pub fn compress(&mut self, config: &Config, rel_path: &str) {
+        if !config.is_compressible_mime_type(self.mime_type.as_deref()) {
+            return;
+        }
+        for encoding in crate::DEFAULT_ENCODING_PREFERENCE.iter() {
+            if self.compressed(*encoding).is_none()
+                && config.should_compress_with(rel_path, *encoding)
+            {
+                if let Some(compressed) = super::compress::compress_cached(
+                    &self.path, &self.hash, &self.data, *encoding, config,
+                ) {
+                    self.compressed.push((*encoding, compressed));
+                }
+            }
+        }
+    }
+
+    /// Use a precompressed version of the file for an encoding, like
+    /// `app.js.br` next to `app.js`. It's only used if it decompresses to the
+    /// contents of the file.
+    pub(crate) fn use_precompressed(&mut self, encoding: Encoding, full_path: &Path) {
+        if let Some(data) = crate::folders::read_precompressed(full_path, encoding, &self.data) {
+            self.compressed
+                .retain(|(compressed, _)| *compressed != encoding);
+            self.compressed.push((encoding, data));
+        }
+    }
+
+    fn compressed(&self, encoding: Encoding) -> Option<Vec<u8>> {
+        self.compressed
+            .iter()
+            .find(|(compressed, _)| *compressed == encoding)
+            .map(|(_, data)| data.clone())
+    }
 }
 
 impl Debug for DynamicFile {
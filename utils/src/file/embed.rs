@@ -17,6 +17,10 @@ pub struct EmbeddedFile {
     data_br: Option<&'static [u8]>,
     #[cfg(feature = "compression-zstd")]
     data_zstd: Option<&'static [u8]>,
+    #[cfg(feature = "compression-deflate")]
+    data_deflate: Option<&'static [u8]>,
+    #[cfg(feature = "compression-zstd")]
+    data_dcz: Option<&'static [u8]>,
     hash: &'static str,
     etag: &'static str,
     last_modified: Option<&'static str>,
@@ -49,6 +53,16 @@ impl EmbedableFile for EmbeddedFile {
         self.data_zstd
     }
 
+    #[cfg(feature = "compression-deflate")]
+    fn data_deflate(&self) -> Option<Self::Data> {
+        self.data_deflate
+    }
+
+    #[cfg(feature = "compression-zstd")]
+    fn data_dcz(&self) -> Option<Self::Data> {
+        self.data_dcz
+    }
+
     fn last_modified(&self) -> Option<Self::Meta> {
         self.last_modified
     }
@@ -72,11 +86,13 @@ impl EmbedableFile for EmbeddedFile {
 
 impl EmbeddedFile {
     #[doc(hidden)]
-    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::too_many_arguments, unused_variables)]
     /// This is used internally in derived code to create embedded file objects.
     /// You don't want to manually use this function!
-    #[cfg(feature = "compression-zstd")]
-    pub fn __internal_make(
+    ///
+    /// The compressed versions for optional encodings are always passed in,
+    /// and ignored if the feature for that encoding is disabled.
+    pub const fn __internal_make(
         // Make sure that the order of these parameters is correct in respect to
         // the file contents! And if you are changing or reordering any of
         // these, make sure to update the corresponding call in `impl`
@@ -85,6 +101,8 @@ impl EmbeddedFile {
         data_gzip: Option<&'static [u8]>,
         data_br: Option<&'static [u8]>,
         data_zstd: Option<&'static [u8]>,
+        data_deflate: Option<&'static [u8]>,
+        data_dcz: Option<&'static [u8]>,
         hash: &'static str,
         etag: &'static str,
         last_modified: Option<&'static str>,
@@ -96,40 +114,12 @@ impl EmbeddedFile {
             data,
             data_gzip,
             data_br,
+            #[cfg(feature = "compression-zstd")]
             data_zstd,
-            hash,
-            etag,
-            last_modified,
-            last_modified_timestamp,
-            mime_type,
-        }
-    }
-
-    #[doc(hidden)]
-    #[allow(clippy::too_many_arguments)]
-    /// This is used internally in derived code to create embedded file objects.
-    /// You don't want to manually use this function!
-    #[cfg(not(feature = "compression-zstd"))]
-    pub fn __internal_make(
-        // Make sure that the order of these parameters is correct in respect to
-        // the file contents! And if you are changing or reordering any of
-        // these, make sure to update the corresponding call in `impl`
-        name: &'static str,
-        data: &'static [u8],
-        data_gzip: Option<&'static [u8]>,
-        data_br: Option<&'static [u8]>,
-        _data_zstd: Option<&'static [u8]>, // Ignored when feature disabled
-        hash: &'static str,
-        etag: &'static str,
-        last_modified: Option<&'static str>,
-        last_modified_timestamp: Option<i64>,
-        mime_type: Option<&'static str>,
-    ) -> EmbeddedFile {
-        EmbeddedFile {
-            name,
-            data,
-            data_gzip,
-            data_br,
+            #[cfg(feature = "compression-deflate")]
+            data_deflate,
+            #[cfg(feature = "compression-zstd")]
+            data_dcz,
             hash,
             etag,
             last_modified,
@@ -1,4 +1,6 @@
 mod common;
+#[cfg(feature = "dynamic-compression")]
+mod compress;
 mod dynamic;
 mod embed;
 
@@ -0,0 +1,130 @@
+//! Dynamic embeds read files from their folders when they are accessed. These
+//! find the same files, along with the same precompressed versions of them,
+//! that a release build embeds.
+use std::path::{Path, PathBuf};
+
+use new_mime_guess::MimeGuess;
+
+use crate::{find_file, precompressed_original, Config, DynamicFile, Encoding};
+
+/// The extensions of precompressed files, see `precompressed_original` for
+/// the encodings they belong to.
+const PRECOMPRESSED_EXTENSIONS: [&str; 3] = ["gz", "br", "zst"];
+
+/// Find the file for a path in the folders of a dynamic embed. Like in release
+/// builds, the file comes from the first folder that has it, and only if the
+/// config includes it.
+///
+/// The folders are the paths of the folders along with their prefixes, in the
+/// order they were listed.
+pub fn find_included_file(
+    folders: &[(&str, &str)],
+    config: &Config,
+    path: &str,
+) -> Option<PathBuf> {
+    if !config.should_include(path) {
+        return None;
+    }
+    folders
+        .iter()
+        .find_map(|(folder_path, prefix)| find_file(folder_path, path.strip_prefix(prefix)?))
+}
+
+/// Whether the precompressed version of a file for an encoding is used, which
+/// is when the file would have been compressed with that encoding.
+fn uses_precompressed(config: &Config, path: &str, encoding: Encoding) -> bool {
+    let mime_type = MimeGuess::from_path(path).first().map(|v| v.to_string());
+    config.is_compressible_mime_type(mime_type.as_deref())
+        && config.should_compress_with(path, encoding)
+}
+
+/// Read a precompressed file, if it decompresses to the contents of its
+/// original.
+#[cfg(feature = "compress")]
+pub(crate) fn read_precompressed(
+    full_path: &Path,
+    encoding: Encoding,
+    original: &[u8],
+) -> Option<Vec<u8>> {
+    let data = std::fs::read(full_path).ok()?;
+    match crate::compress::decompress(encoding, &data) {
+        Ok(decompressed) if decompressed == original => Some(data),
+        _ => None,
+    }
+}
+
+/// Read a precompressed file. Without the decoders it can't be checked here,
+/// but release builds still check it when embedding it.
+#[cfg(not(feature = "compress"))]
+pub(crate) fn read_precompressed(
+    full_path: &Path,
+    _encoding: Encoding,
+    _original: &[u8],
+) -> Option<Vec<u8>> {
+    std::fs::read(full_path).ok()
+}
+
+/// Whether a path is the precompressed version of another file in a dynamic
+/// embed, which hides it like in release builds.
+///
+/// Release builds fail to compile if a precompressed file that would be used
+/// doesn't decompress to its original. Dynamic embeds show that file as a
+/// regular file instead, if they can decompress it, which needs the `compress`
+/// feature.
+pub fn is_hidden_precompressed(folders: &[(&str, &str)], config: &Config, path: &str) -> bool {
+    if !config.use_precompressed() {
+        return false;
+    }
+    let (original, encoding) = match precompressed_original(path) {
+        Some(original) => original,
+        None => return false,
+    };
+    let (original_path, full_path) = match (
+        find_included_file(folders, config, original),
+        find_included_file(folders, config, path),
+    ) {
+        (Some(original_path), Some(full_path)) => (original_path, full_path),
+        _ => return false,
+    };
+    if !uses_precompressed(config, original, encoding) {
+        return true;
+    }
+    std::fs::read(original_path)
+        .ok()
+        .and_then(|original| read_precompressed(&full_path, encoding, &original))
+        .is_some()
+}
+
+/// Read the file for a path in the folders of a dynamic embed.
+///
+/// If the config uses precompressed files, they are served as the compressed
+/// versions of the file, like in release builds. With the
+/// `dynamic-compression` feature, the file is also compressed with the other
+/// encodings.
+pub fn read_dynamic_file(
+    folders: &[(&str, &str)],
+    config: &Config,
+    path: &str,
+) -> Option<DynamicFile> {
+    if is_hidden_precompressed(folders, config, path) {
+        return None;
+    }
+    let full_path = find_included_file(folders, config, path)?;
+    let mut file = DynamicFile::read_from_fs(full_path).ok()?;
+    if config.use_precompressed() {
+        for extension in PRECOMPRESSED_EXTENSIONS.iter() {
+            let precompressed_path = format!("{}.{}", path, extension);
+            // Extensions of encodings that aren't enabled are skipped.
+            let encoding = match precompressed_original(&precompressed_path) {
+                Some((_, encoding)) if uses_precompressed(config, path, encoding) => encoding,
+                _ => continue,
+            };
+            if let Some(full_path) = find_included_file(folders, config, &precompressed_path) {
+                file.use_precompressed(encoding, &full_path);
+            }
+        }
+    }
+    #[cfg(feature = "dynamic-compression")]
+    file.compress(config, path);
+    Some(file)
+}
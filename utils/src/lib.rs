@@ -7,29 +7,60 @@
 mod file;
 pub use file::*;
 
+#[cfg(feature = "compress")]
+pub mod compress;
+
+#[doc(hidden)]
+pub use phf;
+
+mod folders;
+pub use folders::{find_included_file, is_hidden_precompressed, read_dynamic_file};
+
+mod dir;
+pub use dir::{directory_key, directory_tree, DirEntry};
+
 mod config;
-pub use config::Config;
+pub use config::{Config, DEFAULT_INCOMPRESSIBLE_MIME_TYPES};
+
+mod conditional;
+pub use conditional::{ConditionalRequest, ConditionalResponse};
+
+mod encoding;
+pub use encoding::{negotiate_encoding, Encoding, DEFAULT_ENCODING_PREFERENCE};
+
+#[cfg(feature = "compression-zstd")]
+mod dictionary;
+#[cfg(feature = "compression-zstd")]
+pub use dictionary::ZstdDictionary;
 
 pub struct FileEntry {
     pub rel_path: String,
     pub full_canonical_path: String,
 }
 
+/// Find all the files in a folder that should be embedded.
+///
+/// Any errors encountered while walking the folder are returned, including
+/// when the folder itself doesn't exist, so the caller can decide whether to
+/// report or skip them. Files are sorted by their names within each folder, so
+/// the order is the same on every system.
 pub fn get_files<'t>(
     folder_path: &'t str,
     config: &'t Config,
     prefix: &'t str,
-) -> impl Iterator<Item = FileEntry> + 't {
+) -> impl Iterator<Item = std::io::Result<FileEntry>> + 't {
     walkdir::WalkDir::new(folder_path)
         .follow_links(true)
+        .sort_by_file_name()
         .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.as_ref().map_or(true, |e| e.file_type().is_file()))
         .filter_map(move |e| {
+            let e = match e {
+                Ok(e) => e,
+                Err(error) => return Some(Err(error.into())),
+            };
             let rel_path = path_to_str(e.path().strip_prefix(folder_path).unwrap());
             let rel_path = format!("{prefix}{rel_path}");
-            let full_canonical_path =
-                path_to_str(std::fs::canonicalize(e.path()).expect("Could not get canonical path"));
 
             let rel_path = if std::path::MAIN_SEPARATOR == '\\' {
                 rel_path.replace('\\', "/")
@@ -41,13 +72,73 @@ pub fn get_files<'t>(
                 return None;
             }
 
-            Some(FileEntry {
+            let full_canonical_path = match std::fs::canonicalize(e.path()) {
+                Ok(path) => path_to_str(path),
+                Err(error) => {
+                    return Some(Err(std::io::Error::new(
+                        error.kind(),
+                        format!("{}: {}", e.path().display(), error),
+                    )))
+                }
+            };
+
+            Some(Ok(FileEntry {
                 rel_path,
                 full_canonical_path,
-            })
+            }))
         })
 }
 
+/// If a path looks like a precompressed version of another file, like
+/// `app.js.br`, get the path of the original file and the encoding it was
+/// compressed with.
+///
+/// This doesn't check whether the original file exists.
+pub fn precompressed_original(path: &str) -> Option<(&str, Encoding)> {
+    let (original, extension) = path.rsplit_once('.')?;
+    let encoding = match extension {
+        "gz" => Encoding::Gzip,
+        "br" => Encoding::Br,
+        #[cfg(feature = "compression-zstd")]
+        "zst" => Encoding::Zstd,
+        _ => return None,
+    };
+    if original.is_empty() || original.ends_with('/') {
+        return None;
+    }
+    Some((original, encoding))
+}
+
+/// Find the file for a relative path inside of a folder.
+///
+/// This is what dynamic embeds use to find the file at runtime, so it only
+/// accepts paths that could have been found by `get_files` when embedding: the
+/// path has to be relative, made of plain `/` separated components (no `.`,
+/// `..` or empty components), and it must point to a file that is actually
+/// inside of the folder after resolving any symlinks.
+///
+/// The path should not include the prefix, strip that first.
+pub fn find_file(folder_path: &str, rel_path: &str) -> Option<std::path::PathBuf> {
+    let is_plain_component = |component: &str| {
+        let mut components = std::path::Path::new(component).components();
+        matches!(
+            (components.next(), components.next()),
+            (Some(std::path::Component::Normal(_)), None)
+        ) && !component.contains('\\')
+    };
+    if !rel_path.split('/').all(is_plain_component) {
+        return None;
+    }
+
+    let folder_path = std::fs::canonicalize(folder_path).ok()?;
+    let full_path = std::fs::canonicalize(folder_path.join(rel_path)).ok()?;
+    if !full_path.starts_with(&folder_path) || !full_path.is_file() {
+        return None;
+    }
+
+    Some(full_path)
+}
+
 fn path_to_str<P: AsRef<std::path::Path>>(p: P) -> String {
     p.as_ref()
         .to_str()